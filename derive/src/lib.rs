@@ -1,7 +1,8 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
 use syn::{spanned::Spanned, Field};
 
-#[proc_macro_derive(DeviceDestroyable, attributes(destroy_ignore, destroy_ignore_remaining))]
+#[proc_macro_derive(DeviceDestroyable, attributes(destroy_ignore, destroy_ignore_remaining, destroy_with, destroy))]
 pub fn derive_device_destroyable(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let ast = match syn::parse(input) {
         Ok(data) => data,
@@ -9,29 +10,148 @@ pub fn derive_device_destroyable(input: proc_macro::TokenStream) -> proc_macro::
     };
 
     // Build the trait implementation
-    impl_macro(&ast).unwrap_or_else(|err| err.to_compile_error().into())
+    impl_macro(&ast, &DestroyTrait::device()).unwrap_or_else(|err| err.to_compile_error().into())
+}
+
+#[proc_macro_derive(InstanceDestroyable, attributes(destroy_ignore, destroy_ignore_remaining, destroy_with, destroy))]
+pub fn derive_instance_destroyable(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast = match syn::parse(input) {
+        Ok(data) => data,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    impl_macro(&ast, &DestroyTrait::instance()).unwrap_or_else(|err| err.to_compile_error().into())
+}
+
+#[proc_macro_derive(SelfDestroyable, attributes(destroy_ignore, destroy_ignore_remaining, destroy_with, destroy_device, destroy))]
+pub fn derive_self_destroyable(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast = match syn::parse(input) {
+        Ok(data) => data,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    impl_self_macro(&ast).unwrap_or_else(|err| err.to_compile_error().into())
+}
+
+// the nulled fields are the ones destroyed by the other derives, their attributes are validated there.
+// #[destroy_with] fields must be given #[destroy(null_with = "path")] or #[destroy(null_with = none)]
+#[proc_macro_derive(SetNull, attributes(destroy_ignore, destroy_ignore_remaining, destroy_with, destroy_device, destroy))]
+pub fn derive_set_null(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let ast = match syn::parse(input) {
+        Ok(data) => data,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    impl_set_null_macro(&ast).unwrap_or_else(|err| err.to_compile_error().into())
+}
+
+// the derived trait and the extra parameter its destroy function receives besides the allocation callbacks
+struct DestroyTrait {
+    name: &'static str,
+    param_name: syn::Ident,
+    param_ty: TokenStream,
+    // whether a field can be marked as the device owned by the value
+    owns_device: bool,
+}
+
+impl DestroyTrait {
+    fn device() -> Self {
+        Self {
+            name: "DeviceDestroyable",
+            param_name: quote::format_ident!("device"),
+            param_ty: quote::quote! { &ash::Device },
+            owns_device: false,
+        }
+    }
+
+    fn instance() -> Self {
+        Self {
+            name: "InstanceDestroyable",
+            param_name: quote::format_ident!("loaders"),
+            param_ty: quote::quote! { &ash_destructor::InstanceLoaders },
+            owns_device: false,
+        }
+    }
+
+    // the children of a SelfDestroyable value are destroyed with the device it owns
+    fn self_children() -> Self {
+        Self {
+            owns_device: true,
+            ..Self::device()
+        }
+    }
+
+    // the span is used by errors caused by a field not implementing the trait
+    fn path(&self, span: proc_macro2::Span) -> TokenStream {
+        let ident = syn::Ident::new(self.name, span);
+        quote::quote_spanned! {span => ash_destructor::#ident }
+    }
 }
 
 #[derive(Debug, Default)]
 struct FieldAttributes {
     pub destroy_ignore: bool,
+    pub destroy_device: bool,
+    // function called instead of the trait to destroy the field
+    pub destroy_with: Option<syn::Path>,
+    // fields that have to be destroyed after this one
+    pub destroy_before: Vec<syn::Member>,
+    // fields that have to be destroyed before this one
+    pub destroy_after: Vec<syn::Member>,
+    // sibling pool the handles of the field are freed through
+    pub destroy_pool: Option<syn::Member>,
+    // callbacks the field is destroyed with instead of the ones given to the impl
+    pub destroy_alloc: Option<FieldAlloc>,
+}
+
+// #[destroy(alloc = none)] or #[destroy(alloc = self.field)], in enums the field is a sibling in
+// the same variant
+#[derive(Debug)]
+enum FieldAlloc {
+    None(syn::Ident),
+    Field(syn::Member),
+}
+
+impl ToTokens for FieldAlloc {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            FieldAlloc::None(ident) => ident.to_tokens(tokens),
+            FieldAlloc::Field(member) => member.to_tokens(tokens),
+        }
+    }
+}
+
+fn parse_field_alloc(input: syn::parse::ParseStream) -> Result<FieldAlloc, syn::Error> {
+    if input.peek(syn::Token![self]) {
+        input.parse::<syn::Token![self]>()?;
+        input.parse::<syn::Token![.]>()?;
+        return Ok(FieldAlloc::Field(input.parse()?));
+    }
+    const EXPECTED: &str = "Expected `alloc = none` or `alloc = self.field`";
+    match input.parse::<syn::Ident>() {
+        Ok(ident) if ident == "none" => Ok(FieldAlloc::None(ident)),
+        Ok(ident) => Err(syn::Error::new_spanned(ident, EXPECTED)),
+        Err(_) => Err(input.error(EXPECTED)),
+    }
 }
 
 fn parse_attributes<'a>(
     input_name: &syn::Ident,
     fields: &mut impl ExactSizeIterator<Item = &'a Field>,
+    destroy_trait: &DestroyTrait,
     errors: &mut Vec<syn::Error>,
 ) -> (Option<usize>, Vec<FieldAttributes>) {
     let mut field_attrs = Vec::with_capacity(fields.len());
     let mut destroy_ignore_remaining_index = None;
+    let mut destroy_device_index = None;
 
     for (f_i, field) in fields.enumerate() {
         let mut attrs = FieldAttributes::default();
         for attr in field.attrs.iter() {
             if attr.path().is_ident("destroy_ignore_remaining") {
                 if destroy_ignore_remaining_index.is_some() {
-                    errors.push(syn::Error::new(
-                        attr.span(),
+                    errors.push(syn::Error::new_spanned(
+                        attr,
                         format!(
                             "Multiple #[destroy_ignore_remaining] attributes in {:?}",
                             input_name.to_string()
@@ -50,15 +170,15 @@ fn parse_attributes<'a>(
             if attr.path().is_ident("destroy_ignore") {
                 if let Some(destroy_ignore_i) = destroy_ignore_remaining_index {
                     if destroy_ignore_i >= attr_i {
-                        errors.push(syn::Error::new(
-                            attr.span(),
+                        errors.push(syn::Error::new_spanned(
+                            attr,
                             "Attribute #[destroy_ignore] is not allowed after a #[destroy_ignore_remaining] attribute declaration",
                         ));
                     }
                 }
                 if attrs.destroy_ignore {
-                    errors.push(syn::Error::new(
-                        field.span(),
+                    errors.push(syn::Error::new_spanned(
+                        field,
                         "Multiple #[destroy_ignore] attributes on a single field",
                     ));
                 }
@@ -69,108 +189,926 @@ fn parse_attributes<'a>(
             }
         }
 
+        for attr in field.attrs.iter() {
+            if attr.path().is_ident("destroy_with") {
+                if attrs.destroy_with.is_some() {
+                    errors.push(syn::Error::new_spanned(
+                        field,
+                        "Multiple #[destroy_with] attributes on a single field",
+                    ));
+                }
+                if attrs.destroy_ignore || destroy_ignore_remaining_index.is_some_and(|i| i <= f_i) {
+                    errors.push(syn::Error::new_spanned(
+                        attr,
+                        "Attribute #[destroy_with] is not allowed on an ignored field",
+                    ));
+                }
+                match parse_destroy_with(attr) {
+                    Ok(path) => attrs.destroy_with = Some(path),
+                    Err(err) => {
+                        errors.push(err);
+                        // don't report the field not implementing the trait on top of the invalid attribute
+                        attrs.destroy_ignore = true;
+                    }
+                }
+            }
+        }
+
+        for attr in field.attrs.iter() {
+            if attr.path().is_ident("destroy") {
+                let result = attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("before") {
+                        attrs.destroy_before.push(meta.value()?.parse()?);
+                        Ok(())
+                    } else if meta.path.is_ident("after") {
+                        attrs.destroy_after.push(meta.value()?.parse()?);
+                        Ok(())
+                    } else if meta.path.is_ident("pool") {
+                        if attrs.destroy_pool.is_some() {
+                            return Err(meta.error("Multiple pools for a single field"));
+                        }
+                        attrs.destroy_pool = Some(meta.value()?.parse()?);
+                        Ok(())
+                    } else if meta.path.is_ident("alloc") {
+                        if attrs.destroy_alloc.is_some() {
+                            return Err(meta.error("Multiple allocation callbacks for a single field"));
+                        }
+                        attrs.destroy_alloc = Some(parse_field_alloc(meta.value()?)?);
+                        Ok(())
+                    } else if meta.path.is_ident("null_with") {
+                        // used by #[derive(SetNull)]
+                        parse_null_with(meta.value()?)?;
+                        Ok(())
+                    } else {
+                        Err(meta.error(
+                            "Unsupported destroy attribute, expected `before = field`, `after = field`, `pool = field`, `alloc = ...` or `null_with = \"path\"`",
+                        ))
+                    }
+                });
+                if let Err(err) = result {
+                    errors.push(err);
+                }
+            }
+        }
+
+        if let Some(pool) = &attrs.destroy_pool {
+            if destroy_trait.name != "DeviceDestroyable" {
+                errors.push(syn::Error::new_spanned(
+                    pool,
+                    format!("Freeing through a pool is not supported by {}", destroy_trait.name),
+                ));
+                // don't report the field not implementing the trait on top of the invalid attribute
+                attrs.destroy_ignore = true;
+            } else if attrs.destroy_with.is_some() {
+                errors.push(syn::Error::new_spanned(
+                    pool,
+                    "A field freed through a pool cannot also use #[destroy_with]",
+                ));
+            } else if attrs.destroy_ignore || destroy_ignore_remaining_index.is_some_and(|i| i <= f_i) {
+                errors.push(syn::Error::new_spanned(pool, "An ignored field cannot be freed through a pool"));
+            }
+        }
+
+        if let Some(alloc) = &attrs.destroy_alloc {
+            if attrs.destroy_pool.is_some() {
+                errors.push(syn::Error::new_spanned(
+                    alloc,
+                    "A field freed through a pool is freed without allocation callbacks",
+                ));
+            } else if attrs.destroy_ignore || destroy_ignore_remaining_index.is_some_and(|i| i <= f_i) {
+                errors.push(syn::Error::new_spanned(
+                    alloc,
+                    "An ignored field cannot be destroyed with allocation callbacks",
+                ));
+            }
+        }
+
+        for attr in field.attrs.iter() {
+            if attr.path().is_ident("destroy_device") {
+                if !destroy_trait.owns_device {
+                    errors.push(syn::Error::new_spanned(
+                        attr,
+                        "Attribute #[destroy_device] is only supported by #[derive(SelfDestroyable)]",
+                    ));
+                    continue;
+                }
+                if attrs.destroy_device {
+                    errors.push(syn::Error::new_spanned(
+                        field,
+                        "Multiple #[destroy_device] attributes on a single field",
+                    ));
+                } else if destroy_device_index.is_some() {
+                    errors.push(syn::Error::new_spanned(
+                        attr,
+                        format!("Multiple #[destroy_device] fields in {:?}", input_name.to_string()),
+                    ));
+                }
+                if let Err(err) = attr.meta.require_path_only() {
+                    errors.push(err);
+                }
+                if attrs.destroy_with.is_some() {
+                    errors.push(syn::Error::new_spanned(
+                        attr,
+                        "The #[destroy_device] field is always destroyed as a SelfDestroyable and cannot use #[destroy_with]",
+                    ));
+                }
+                if attrs.destroy_ignore || destroy_ignore_remaining_index.is_some_and(|i| i <= f_i) {
+                    errors.push(syn::Error::new_spanned(
+                        attr,
+                        "The #[destroy_device] field is always destroyed and cannot be ignored",
+                    ));
+                }
+                if let Some(alloc) = &attrs.destroy_alloc {
+                    errors.push(syn::Error::new_spanned(
+                        alloc,
+                        "The #[destroy_device] field is always destroyed with the given allocation callbacks",
+                    ));
+                }
+                attrs.destroy_device = true;
+                destroy_device_index = Some(f_i);
+            }
+        }
+
         field_attrs.push(attrs);
     }
 
     (destroy_ignore_remaining_index, field_attrs)
 }
 
-struct FunctionDestroyStmtsFieldIterator<
-    'a,
-    T: ExactSizeIterator<Item = &'a Field> + DoubleEndedIterator<Item = &'a Field>,
-> {
-    fields_iter: std::iter::Rev<std::iter::Enumerate<&'a mut T>>,
+// helper attribute values must be literals, so the path is given as a string
+fn parse_destroy_with(attr: &syn::Attribute) -> Result<syn::Path, syn::Error> {
+    let name_value = attr.meta.require_name_value()?;
+    match &name_value.value {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit), ..
+        }) => lit.parse(),
+        value => Err(syn::Error::new_spanned(
+            value,
+            "Expected a function path in a string, e.g. #[destroy_with = \"my_mod::destroy_field\"]",
+        )),
+    }
+}
+
+struct FunctionDestroyStmtsFieldIterator<'a> {
+    fields: Vec<&'a Field>,
+    destroy_order: std::vec::IntoIter<usize>,
     field_attributes: &'a Vec<FieldAttributes>,
+    field_accessors: &'a Vec<FieldAccess>,
+    destroy_trait: &'a DestroyTrait,
+    pools: &'a [Option<usize>],
+    allocs: &'a [Option<TokenStream>],
 }
 
-impl<'a, T: ExactSizeIterator<Item = &'a Field> + DoubleEndedIterator<Item = &'a Field>>
-    FunctionDestroyStmtsFieldIterator<'a, T>
-{
+impl<'a> FunctionDestroyStmtsFieldIterator<'a> {
     fn new(
-        fields: &'a mut T,
+        fields: &'a syn::Fields,
         field_attributes: &'a Vec<FieldAttributes>,
-        destroy_ignore_everything_after: usize,
+        field_accessors: &'a Vec<FieldAccess>,
+        destroy_trait: &'a DestroyTrait,
+        destroy_order: Vec<usize>,
+        pools: &'a [Option<usize>],
+        allocs: &'a [Option<TokenStream>],
     ) -> Self {
-        let fields_len = fields.len();
-        let mut fields_iter = fields.enumerate().rev();
-        // destroy_ignore all elements after destroy_ignore_everything_after
-        for _ in 0..(fields_len - destroy_ignore_everything_after) {
-            let _ = fields_iter.next();
-        }
-
         Self {
-            fields_iter,
+            fields: fields.iter().collect(),
+            destroy_order: destroy_order.into_iter(),
             field_attributes,
+            field_accessors,
+            destroy_trait,
+            pools,
+            allocs,
         }
     }
 }
 
-impl<'a, T: ExactSizeIterator<Item = &'a Field> + DoubleEndedIterator<Item = &'a Field>> Iterator
-    for FunctionDestroyStmtsFieldIterator<'a, T>
-{
+impl Iterator for FunctionDestroyStmtsFieldIterator<'_> {
     type Item = TokenStream;
 
     fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            let (i, field) = self.fields_iter.next()?;
-            let attrs = &self.field_attributes[i];
-
-            if !attrs.destroy_ignore {
-                return Some(if let Some(ident) = field.ident.as_ref() {
-                    quote::quote_spanned! {field.span() =>
-                        ash_destructor::DeviceDestroyable::destroy_self_alloc(&self.#ident, device, allocation_callbacks);
+        let i = self.destroy_order.next()?;
+        let field = self.fields[i];
+        let attrs = &self.field_attributes[i];
+
+        let FieldAccess {
+            expr,
+            owner,
+            path: field_path,
+        } = &self.field_accessors[i];
+        let path = self.destroy_trait.path(field.span());
+        let param_name = &self.destroy_trait.param_name;
+        // the given callbacks are spanned like the call so that signature errors point at it
+        let alloc = |span| {
+            self.allocs[i]
+                .clone()
+                .unwrap_or_else(|| quote::quote_spanned! {span => allocation_callbacks })
+        };
+        let destroy = match (&attrs.destroy_with, self.pools[i]) {
+            (Some(destroy_with), _) => {
+                let alloc = alloc(destroy_with.span());
+                quote::quote_spanned! {destroy_with.span() =>
+                    #destroy_with(#expr, #param_name, #alloc);
+                }
+            }
+            (None, Some(pool)) => {
+                let pool_expr = &self.field_accessors[pool].expr;
+                quote::quote_spanned! {field.span() =>
+                    ash_destructor::__private::free_pooled(#expr, #pool_expr, #param_name);
+                }
+            }
+            (None, None) => {
+                let alloc = alloc(field.span());
+                quote::quote_spanned! {field.span() =>
+                    #path::destroy_self_alloc(#expr, #param_name, #alloc);
+                }
+            }
+        };
+        Some(quote::quote! {
+            {
+                let _field_scope = ash_destructor::__private::FieldScope::field(#owner, #field_path);
+                #destroy
+            }
+        })
+    }
+}
+
+// fields are destroyed in reverse declaration order, unless #[destroy(before = ..., after = ...)]
+// constraints require otherwise, in which case the order closest to the reverse declaration order
+// that satisfies them is used
+fn destroy_order(
+    name: &syn::Ident,
+    fields: &syn::Fields,
+    field_attributes: &[FieldAttributes],
+    destroyed: &[bool],
+    pools: &[Option<usize>],
+    errors: &mut Vec<syn::Error>,
+) -> Vec<usize> {
+    let fields: Vec<&Field> = fields.iter().collect();
+    let find_field = |member: &syn::Member, errors: &mut Vec<syn::Error>| match find_field(&fields, member) {
+        Some(index) if destroyed[index] => Some(index),
+        Some(_) => {
+            errors.push(syn::Error::new_spanned(
+                member,
+                "Field is not destroyed by this impl and cannot be ordered against",
+            ));
+            None
+        }
+        None => {
+            errors.push(syn::Error::new_spanned(
+                member,
+                format!("Unknown field in {:?}", name.to_string()),
+            ));
+            None
+        }
+    };
+
+    // (first, then, constraint) edges, `first` must be destroyed before `then`
+    let mut edges = Vec::new();
+    for (i, attrs) in field_attributes.iter().enumerate() {
+        // handles are freed before their pool is destroyed, a pool that isn't destroyed imposes nothing
+        if let (Some(pool), Some(member)) = (pools[i], &attrs.destroy_pool) {
+            if destroyed[pool] {
+                edges.push((i, pool, member));
+            }
+        }
+        for member in attrs.destroy_before.iter() {
+            if let Some(other) = find_field(member, errors) {
+                edges.push((i, other, member));
+            }
+        }
+        for member in attrs.destroy_after.iter() {
+            if let Some(other) = find_field(member, errors) {
+                edges.push((other, i, member));
+            }
+        }
+    }
+
+    let mut remaining: Vec<usize> = (0..fields.len()).filter(|i| destroyed[*i]).collect();
+    let mut order = Vec::with_capacity(remaining.len());
+    while !remaining.is_empty() {
+        // the last declared field that doesn't have to wait for any other remaining field
+        let next = remaining
+            .iter()
+            .rposition(|i| !edges.iter().any(|(first, then, _)| then == i && remaining.contains(first)));
+        let Some(next) = next else {
+            let (_, _, member) = edges
+                .iter()
+                .find(|(first, then, _)| remaining.contains(first) && remaining.contains(then))
+                .expect("remaining fields without a free field must have an edge between them");
+            let unordered = remaining
+                .iter()
+                .map(|i| field_name(*i, fields[*i]))
+                .collect::<Vec<_>>()
+                .join(", ");
+            errors.push(syn::Error::new_spanned(
+                member,
+                format!("Cyclic destroy order, fields {} cannot be ordered", unordered),
+            ));
+            order.extend(remaining.iter().rev());
+            break;
+        };
+        order.push(remaining.remove(next));
+    }
+
+    order
+}
+
+fn find_field(fields: &[&Field], member: &syn::Member) -> Option<usize> {
+    fields.iter().enumerate().position(|(i, field)| match (member, &field.ident) {
+        (syn::Member::Named(member), Some(ident)) => member == ident,
+        (syn::Member::Unnamed(member), None) => member.index as usize == i,
+        _ => false,
+    })
+}
+
+// the index of the pool each field is freed through
+fn resolve_pools(
+    name: &syn::Ident,
+    fields: &syn::Fields,
+    field_attributes: &[FieldAttributes],
+    errors: &mut Vec<syn::Error>,
+) -> Vec<Option<usize>> {
+    let fields: Vec<&Field> = fields.iter().collect();
+    field_attributes
+        .iter()
+        .map(|attrs| {
+            let member = attrs.destroy_pool.as_ref()?;
+            let pool = find_field(&fields, member);
+            if pool.is_none() {
+                errors.push(syn::Error::new_spanned(
+                    member,
+                    format!("Unknown field in {:?}", name.to_string()),
+                ));
+            }
+            pool
+        })
+        .collect()
+}
+
+// the index of the field holding the allocation callbacks each field is destroyed with
+fn resolve_allocs(
+    name: &syn::Ident,
+    fields: &syn::Fields,
+    field_attributes: &[FieldAttributes],
+    errors: &mut Vec<syn::Error>,
+) -> Vec<Option<usize>> {
+    let fields: Vec<&Field> = fields.iter().collect();
+    field_attributes
+        .iter()
+        .map(|attrs| {
+            let Some(FieldAlloc::Field(member)) = &attrs.destroy_alloc else {
+                return None;
+            };
+            let alloc = find_field(&fields, member);
+            if alloc.is_none() {
+                errors.push(syn::Error::new_spanned(
+                    member,
+                    format!("Unknown field in {:?}", name.to_string()),
+                ));
+            }
+            alloc
+        })
+        .collect()
+}
+
+// how a derived impl reaches a field and how the field is named in destruction paths
+struct FieldAccess {
+    expr: TokenStream,
+    // name of the struct or enum declaring the field
+    owner: String,
+    path: String,
+}
+
+fn field_name(i: usize, field: &Field) -> String {
+    match &field.ident {
+        Some(ident) => ident.to_string(),
+        None => i.to_string(),
+    }
+}
+
+// the fields of a variant are accessed through bindings created by its match arm
+fn variant_binding(i: usize, field: &Field) -> syn::Ident {
+    quote::format_ident!("__field_{}", i, span = field.span())
+}
+
+// returns the destroy statements of the given fields, whether each field is used by them and the
+// types of the fields destroyed through the trait
+fn destroy_stmts(
+    name: &syn::Ident,
+    fields: &syn::Fields,
+    field_accessors: &Vec<FieldAccess>,
+    destroy_trait: &DestroyTrait,
+    errors: &mut Vec<syn::Error>,
+) -> (Vec<TokenStream>, Vec<bool>, Vec<syn::Type>) {
+    let (destroy_ignore_after, field_attributes) = parse_attributes(name, &mut fields.iter(), destroy_trait, errors);
+    let destroy_ignore_after = destroy_ignore_after.unwrap_or(fields.len());
+
+    let pools = resolve_pools(name, fields, &field_attributes, errors);
+    let alloc_fields = resolve_allocs(name, fields, &field_attributes, errors);
+    // fields without an attribute use the given callbacks, and so do fields whose callbacks field
+    // couldn't be found as the error has already been reported
+    let allocs: Vec<Option<TokenStream>> = field_attributes
+        .iter()
+        .zip(&alloc_fields)
+        .map(|(attrs, alloc_field)| match (&attrs.destroy_alloc, alloc_field) {
+            (Some(FieldAlloc::None(ident)), _) => Some(quote::quote_spanned! {ident.span() => std::option::Option::None }),
+            (Some(FieldAlloc::Field(member)), Some(alloc_field)) => {
+                let alloc_expr = &field_accessors[*alloc_field].expr;
+                Some(quote::quote_spanned! {member.span() =>
+                    ash_destructor::AsAllocationCallbacks::as_allocation_callbacks(#alloc_expr).as_ref()
+                })
+            }
+            _ => None,
+        })
+        .collect();
+
+    // the owned device is destroyed after every other field by the SelfDestroyable impl, fields
+    // whose pool couldn't be found are skipped as the error has already been reported
+    let destroyed: Vec<bool> = field_attributes
+        .iter()
+        .enumerate()
+        .map(|(i, attrs)| {
+            i < destroy_ignore_after
+                && !attrs.destroy_ignore
+                && !attrs.destroy_device
+                && (attrs.destroy_pool.is_none() || pools[i].is_some())
+        })
+        .collect();
+
+    let order = destroy_order(name, fields, &field_attributes, &destroyed, &pools, errors);
+    let bounded_types = fields
+        .iter()
+        .zip(&field_attributes)
+        .enumerate()
+        .filter(|(i, (_, attrs))| destroyed[*i] && attrs.destroy_with.is_none() && pools[*i].is_none())
+        .map(|(_, (field, _))| field.ty.clone())
+        .collect();
+    let stmts = FunctionDestroyStmtsFieldIterator::new(
+        fields,
+        &field_attributes,
+        field_accessors,
+        destroy_trait,
+        order,
+        &pools,
+        &allocs,
+    )
+    .collect();
+
+    // pools are used to free their handles even when they aren't destroyed themselves, and so are
+    // the callbacks fields other fields are destroyed with
+    let mut used = destroyed.clone();
+    for (i, links) in pools.iter().zip(&alloc_fields).enumerate() {
+        for linked in [links.0, links.1].into_iter().flatten() {
+            used[*linked] |= destroyed[i];
+        }
+    }
+
+    (stmts, used, bounded_types)
+}
+
+fn struct_field_access(name: &syn::Ident, i: usize, field: &Field) -> FieldAccess {
+    let expr = if let Some(ident) = field.ident.as_ref() {
+        quote::quote_spanned! {field.span() => &self.#ident }
+    } else {
+        let tuple_i = syn::Index::from(i);
+        quote::quote_spanned! {field.span() => &self.#tuple_i }
+    };
+    FieldAccess {
+        expr,
+        owner: name.to_string(),
+        path: field_name(i, field),
+    }
+}
+
+fn struct_destroy_body(
+    name: &syn::Ident,
+    fields: &syn::Fields,
+    destroy_trait: &DestroyTrait,
+    errors: &mut Vec<syn::Error>,
+) -> (TokenStream, Vec<syn::Type>) {
+    let field_accessors = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| struct_field_access(name, i, field))
+        .collect();
+
+    let (stmts, _, bounded_types) = destroy_stmts(name, fields, &field_accessors, destroy_trait, errors);
+    (quote::quote! { #(#stmts)* }, bounded_types)
+}
+
+fn enum_destroy_body(
+    name: &syn::Ident,
+    data: &syn::DataEnum,
+    destroy_trait: &DestroyTrait,
+    errors: &mut Vec<syn::Error>,
+) -> (TokenStream, Vec<syn::Type>) {
+    if data.variants.is_empty() {
+        return (quote::quote! { match *self {} }, Vec::new());
+    }
+
+    let mut bounded_types = Vec::new();
+    let arms: Vec<TokenStream> = data.variants.iter().map(|variant| {
+        let variant_name = &variant.ident;
+        let field_accessors = variant
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let binding = variant_binding(i, field);
+                FieldAccess {
+                    expr: quote::quote! { #binding },
+                    owner: name.to_string(),
+                    path: format!("{}.{}", variant_name, field_name(i, field)),
+                }
+            })
+            .collect();
+
+        let (stmts, used, variant_bounded_types) =
+            destroy_stmts(variant_name, &variant.fields, &field_accessors, destroy_trait, errors);
+        bounded_types.extend(variant_bounded_types);
+
+        // only bind the fields that are used so that ignored fields don't trigger unused warnings
+        let bindings = variant.fields.iter().enumerate().map(|(i, field)| {
+            let binding = variant_binding(i, field);
+            match (&field.ident, used[i]) {
+                (Some(ident), true) => quote::quote! { #ident: #binding },
+                (Some(_), false) => quote::quote! {},
+                (None, true) => quote::quote! { #binding },
+                (None, false) => quote::quote! { _ },
+            }
+        });
+        let pattern = match &variant.fields {
+            syn::Fields::Named(_) => {
+                let bindings = bindings.filter(|binding| !binding.is_empty());
+                quote::quote! { Self::#variant_name { #(#bindings,)* .. } }
+            }
+            syn::Fields::Unnamed(_) => quote::quote! { Self::#variant_name ( #(#bindings),* ) },
+            syn::Fields::Unit => quote::quote! { Self::#variant_name },
+        };
+
+        quote::quote! {
+            #pattern => {
+                #(#stmts)*
+            }
+        }
+    }).collect();
+
+    let body = quote::quote! {
+        match self {
+            #(#arms)*
+        }
+    };
+    (body, bounded_types)
+}
+
+// parses the #[destroy(bound = "...")] container attribute replacing the inferred bounds
+fn parse_container_bound(attrs: &[syn::Attribute], errors: &mut Vec<syn::Error>) -> Option<Vec<syn::WherePredicate>> {
+    let mut bound = None;
+    for attr in attrs.iter() {
+        if attr.path().is_ident("destroy") {
+            let result = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("bound") {
+                    if bound.is_some() {
+                        return Err(meta.error("Multiple bounds for a single type"));
                     }
+                    let lit: syn::LitStr = meta.value()?.parse()?;
+                    let predicates = lit.parse_with(
+                        syn::punctuated::Punctuated::<syn::WherePredicate, syn::Token![,]>::parse_terminated,
+                    )?;
+                    bound = Some(predicates.into_iter().collect());
+                    Ok(())
                 } else {
-                    let tuple_i = syn::Index::from(i);
-                    quote::quote_spanned! {field.span() =>
-                        ash_destructor::DeviceDestroyable::destroy_self_alloc(&self.#tuple_i, device, allocation_callbacks);
-                    }
-                });
+                    Err(meta.error("Unsupported destroy attribute, expected `bound = \"...\"`"))
+                }
+            });
+            if let Err(err) = result {
+                errors.push(err);
             }
         }
     }
+    bound
+}
+
+fn mentions_type_param(tokens: TokenStream, type_params: &[&syn::Ident]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => type_params.contains(&&ident),
+        TokenTree::Group(group) => mentions_type_param(group.stream(), type_params),
+        _ => false,
+    })
+}
+
+// adds a `FieldType: Trait` predicate for every destroyed field whose type depends on a type
+// parameter, unless the bounds were given with #[destroy(bound = "...")]
+fn bounded_generics(
+    ast: &syn::DeriveInput,
+    bounded_types: &[(syn::Type, TokenStream)],
+    container_bound: Option<Vec<syn::WherePredicate>>,
+) -> syn::Generics {
+    let mut generics = ast.generics.clone();
+    let predicates = match container_bound {
+        Some(predicates) => predicates,
+        None => {
+            let type_params: Vec<&syn::Ident> = ast.generics.type_params().map(|param| &param.ident).collect();
+            let mut predicates: Vec<syn::WherePredicate> = Vec::new();
+            for (ty, trait_path) in bounded_types.iter() {
+                if !mentions_type_param(ty.to_token_stream(), &type_params) {
+                    continue;
+                }
+                let predicate = syn::parse_quote! { #ty: #trait_path };
+                if !predicates.contains(&predicate) {
+                    predicates.push(predicate);
+                }
+            }
+            predicates
+        }
+    };
+    generics.make_where_clause().predicates.extend(predicates);
+    generics
 }
 
-fn impl_macro(ast: &syn::DeriveInput) -> Result<proc_macro::TokenStream, syn::Error> {
+fn union_error(data: &syn::DataUnion, destroy_trait: &str) -> syn::Error {
+    syn::Error::new_spanned(
+        data.union_token,
+        format!(
+            "Unions cannot be destroyed safely as their active field is unknown, implement {} manually",
+            destroy_trait
+        ),
+    )
+}
+
+fn impl_macro(
+    ast: &syn::DeriveInput,
+    destroy_trait: &DestroyTrait,
+) -> Result<proc_macro::TokenStream, syn::Error> {
     let name = &ast.ident;
 
+    let mut errors = Vec::new();
+    let (destroy_body, bounded_types) = match &ast.data {
+        syn::Data::Struct(data) => struct_destroy_body(name, &data.fields, destroy_trait, &mut errors),
+        syn::Data::Enum(data) => enum_destroy_body(name, data, destroy_trait, &mut errors),
+        syn::Data::Union(data) => return Err(union_error(data, destroy_trait.name)),
+    };
+
+    let path = destroy_trait.path(proc_macro2::Span::call_site());
+    let bounded_types: Vec<_> = bounded_types.into_iter().map(|ty| (ty, path.clone())).collect();
+    let container_bound = parse_container_bound(&ast.attrs, &mut errors);
+    let generics = bounded_generics(ast, &bounded_types, container_bound);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let DestroyTrait {
+        param_name, param_ty, ..
+    } = destroy_trait;
+    let stream_errors = errors.iter().map(syn::Error::to_compile_error);
+    let gen = quote::quote! {
+        impl #impl_generics #path for #name #ty_generics #where_clause {
+            unsafe fn destroy_self_alloc(&self, #param_name: #param_ty, allocation_callbacks: std::option::Option<&ash::vk::AllocationCallbacks<'_>>) {
+                #destroy_body
+            }
+
+            #(#stream_errors)*
+        }
+    };
+
+    Ok(gen.into())
+}
+
+fn impl_self_macro(ast: &syn::DeriveInput) -> Result<proc_macro::TokenStream, syn::Error> {
+    let name = &ast.ident;
     let fields = match &ast.data {
         syn::Data::Struct(data) => &data.fields,
+        syn::Data::Union(data) => return Err(union_error(data, "SelfDestroyable")),
         syn::Data::Enum(_) => {
             return Err(syn::Error::new(
                 ast.span(),
-                "Enums are currently unsupported",
-            ))
-        }
-        syn::Data::Union(_) => {
-            return Err(syn::Error::new(
-                ast.span(),
-                "Unions are currently unsupported",
+                "SelfDestroyable can only be derived for structs",
             ))
         }
     };
 
-    let mut errors = Vec::new();
-    let (destroy_ignore_after, field_attributes) = parse_attributes(name, &mut fields.iter(), &mut errors);
+    let Some((device_i, device_field)) = fields
+        .iter()
+        .enumerate()
+        .find(|(_, field)| field.attrs.iter().any(|attr| attr.path().is_ident("destroy_device")))
+    else {
+        return Err(syn::Error::new(
+            name.span(),
+            format!(
+                "{:?} has no field marked as its device, mark the field holding the ash::Device with #[destroy_device]",
+                name.to_string()
+            ),
+        ));
+    };
 
-    let function_fields_iter = &mut fields.iter();
-    let function_destroy_stmts_iter = FunctionDestroyStmtsFieldIterator::new(
-        function_fields_iter,
-        &field_attributes,
-        destroy_ignore_after.unwrap_or(fields.len()),
-    );
+    let mut errors = Vec::new();
+    let destroy_trait = DestroyTrait::self_children();
+    let (children, children_types) = struct_destroy_body(name, fields, &destroy_trait, &mut errors);
 
-    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let FieldAccess {
+        expr: device_expr,
+        owner: device_owner,
+        path: device_path,
+    } = struct_field_access(name, device_i, device_field);
+    let param_name = &destroy_trait.param_name;
+    // only bind the device when it is used so that structs without other fields don't trigger unused warnings
+    let device_binding = if children.is_empty() {
+        quote::quote! {}
+    } else {
+        quote::quote_spanned! {device_field.span() => let #param_name: &ash::Device = #device_expr; }
+    };
+    let self_path = {
+        let ident = syn::Ident::new("SelfDestroyable", device_field.span());
+        quote::quote_spanned! {device_field.span() => ash_destructor::#ident }
+    };
 
+    let device_path_trait = DestroyTrait::device().path(proc_macro2::Span::call_site());
+    let mut bounded_types: Vec<_> = children_types
+        .into_iter()
+        .map(|ty| (ty, device_path_trait.clone()))
+        .collect();
+    bounded_types.push((device_field.ty.clone(), quote::quote! { ash_destructor::SelfDestroyable }));
+    let container_bound = parse_container_bound(&ast.attrs, &mut errors);
+    let generics = bounded_generics(ast, &bounded_types, container_bound);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let stream_errors = errors.iter().map(syn::Error::to_compile_error);
     let gen = quote::quote! {
-        impl #impl_generics ash_destructor::DeviceDestroyable for #name #ty_generics #where_clause {
-            unsafe fn destroy_self_alloc(&self, device: &ash::Device, allocation_callbacks: std::option::Option<&ash::vk::AllocationCallbacks<'_>>) {
-                #(#function_destroy_stmts_iter)*
+        impl #impl_generics ash_destructor::SelfDestroyable for #name #ty_generics #where_clause {
+            unsafe fn destroy_self_alloc(&self, allocation_callbacks: std::option::Option<&ash::vk::AllocationCallbacks<'_>>) {
+                #device_binding
+                #children
+                {
+                    let _field_scope = ash_destructor::__private::FieldScope::field(#device_owner, #device_path);
+                    #self_path::destroy_self_alloc(#device_expr, allocation_callbacks);
+                }
             }
 
             #(#stream_errors)*
         }
+
+        // the value already owns its device, like the impl for ash::Device the given one is ignored
+        impl #impl_generics #device_path_trait for #name #ty_generics #where_clause {
+            unsafe fn destroy_self_alloc(&self, _: &ash::Device, allocation_callbacks: std::option::Option<&ash::vk::AllocationCallbacks<'_>>) {
+                ash_destructor::SelfDestroyable::destroy_self_alloc(self, allocation_callbacks);
+            }
+        }
+    };
+
+    Ok(gen.into())
+}
+
+// how #[derive(SetNull)] resets a field
+enum NullField {
+    Skip,
+    SetNull,
+    // #[destroy(null_with = "path")]
+    With(syn::Path),
+}
+
+// #[destroy(null_with = "path")] or #[destroy(null_with = none)] to leave the field untouched
+fn parse_null_with(input: syn::parse::ParseStream) -> Result<Option<syn::Path>, syn::Error> {
+    if input.peek(syn::LitStr) {
+        return input.parse::<syn::LitStr>()?.parse().map(Some);
+    }
+    const EXPECTED: &str = "Expected `null_with = \"path\"` or `null_with = none`";
+    match input.parse::<syn::Ident>() {
+        Ok(ident) if ident == "none" => Ok(None),
+        Ok(ident) => Err(syn::Error::new_spanned(ident, EXPECTED)),
+        Err(_) => Err(input.error(EXPECTED)),
+    }
+}
+
+// #[destroy_with] fields are usually foreign types that can't implement SetNull, so they have to
+// say how they are reset, otherwise destroying again after destroy_and_null would free them twice
+fn nulled_fields(fields: &syn::Fields) -> Result<Vec<NullField>, syn::Error> {
+    let mut ignore_remaining = false;
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let has_attr = |name: &str| field.attrs.iter().any(|attr| attr.path().is_ident(name));
+            ignore_remaining |= has_attr("destroy_ignore_remaining");
+            if ignore_remaining || has_attr("destroy_ignore") || has_attr("destroy_device") {
+                return Ok(NullField::Skip);
+            }
+            let mut null_with = None;
+            for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("destroy")) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("null_with") {
+                        null_with = Some(parse_null_with(meta.value()?)?);
+                    } else {
+                        // validated by the destroy derives
+                        meta.value()?.parse::<syn::Expr>()?;
+                    }
+                    Ok(())
+                })?;
+            }
+            Ok(match null_with {
+                Some(Some(path)) => NullField::With(path),
+                Some(None) => NullField::Skip,
+                None if has_attr("destroy_with") => {
+                    let name = field.ident.as_ref().map_or_else(|| i.to_string(), ToString::to_string);
+                    let attr = field.attrs.iter().find(|attr| attr.path().is_ident("destroy_with"));
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        format!(
+                            "Field {name} is destroyed with #[destroy_with] and cannot be reset by SetNull, add #[destroy(null_with = \"path\")] or #[destroy(null_with = none)] to leave it untouched",
+                        ),
+                    ));
+                }
+                None => NullField::SetNull,
+            })
+        })
+        .collect()
+}
+
+fn impl_set_null_macro(ast: &syn::DeriveInput) -> Result<proc_macro::TokenStream, syn::Error> {
+    let name = &ast.ident;
+    let set_null = |expr: TokenStream, field: &Field, nulled: &NullField| match nulled {
+        NullField::With(path) => quote::quote_spanned! {path.span() => #path(#expr); },
+        _ => {
+            let ident = syn::Ident::new("SetNull", field.span());
+            quote::quote_spanned! {field.span() => ash_destructor::#ident::set_null(#expr); }
+        }
+    };
+
+    let mut bounded_types = Vec::new();
+    let body = match &ast.data {
+        syn::Data::Struct(data) => {
+            let nulled = nulled_fields(&data.fields)?;
+            let stmts: Vec<TokenStream> = data
+                .fields
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| !matches!(nulled[*i], NullField::Skip))
+                .map(|(i, field)| {
+                    if let NullField::SetNull = nulled[i] {
+                        bounded_types.push(field.ty.clone());
+                    }
+                    let member = match &field.ident {
+                        Some(ident) => syn::Member::Named(ident.clone()),
+                        None => syn::Member::Unnamed(syn::Index::from(i)),
+                    };
+                    set_null(quote::quote! { &mut self.#member }, field, &nulled[i])
+                })
+                .collect();
+            quote::quote! { #(#stmts)* }
+        }
+        syn::Data::Enum(data) => {
+            let mut arms = Vec::new();
+            for variant in data.variants.iter() {
+                let variant_name = &variant.ident;
+                let nulled = nulled_fields(&variant.fields)?;
+                let mut stmts = Vec::new();
+                let bindings: Vec<TokenStream> = variant
+                    .fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, field)| {
+                        let binding = variant_binding(i, field);
+                        let is_nulled = !matches!(nulled[i], NullField::Skip);
+                        if is_nulled {
+                            if let NullField::SetNull = nulled[i] {
+                                bounded_types.push(field.ty.clone());
+                            }
+                            stmts.push(set_null(quote::quote! { #binding }, field, &nulled[i]));
+                        }
+                        match (&field.ident, is_nulled) {
+                            (Some(ident), true) => quote::quote! { #ident: #binding, },
+                            (Some(_), false) => quote::quote! {},
+                            (None, true) => quote::quote! { #binding, },
+                            (None, false) => quote::quote! { _, },
+                        }
+                    })
+                    .collect();
+                let pattern = match &variant.fields {
+                    syn::Fields::Named(_) => quote::quote! { Self::#variant_name { #(#bindings)* .. } },
+                    syn::Fields::Unnamed(_) => quote::quote! { Self::#variant_name ( #(#bindings)* ) },
+                    syn::Fields::Unit => quote::quote! { Self::#variant_name },
+                };
+                arms.push(quote::quote! {
+                    #pattern => {
+                        #(#stmts)*
+                    }
+                });
+            }
+            quote::quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        syn::Data::Union(data) => return Err(union_error(data, "SetNull")),
+    };
+
+    // the bounds given with #[destroy(bound = "...")] are meant for the destroy traits
+    let set_null_path = quote::quote! { ash_destructor::SetNull };
+    let bounded_types: Vec<_> = bounded_types.into_iter().map(|ty| (ty, set_null_path.clone())).collect();
+    let generics = bounded_generics(ast, &bounded_types, None);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let gen = quote::quote! {
+        impl #impl_generics ash_destructor::SetNull for #name #ty_generics #where_clause {
+            fn set_null(&mut self) {
+                #body
+            }
+        }
     };
 
     Ok(gen.into())
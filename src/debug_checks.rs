@@ -0,0 +1,94 @@
+// catches handles destroyed twice and null handles being destroyed, null handles only reach the
+// checks with NullHandlePolicy::Destroy
+//
+// drivers hand out the raw value of a destroyed handle to new objects, so a destroyed value alone
+// doesn't mean a double destruction. Only handles reported with created, or crate::track_created,
+// are checked: every creation of a checked handle must be reported, otherwise destroying a new
+// object reusing its raw value is reported as a double destruction. The handles of a device or
+// instance are forgotten when it is destroyed.
+
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+use ash::vk::{self, Handle};
+
+// whether each reported handle has been destroyed, indexed by the raw value of its parent.
+// devices and instances are kept under 0 as they outlive the handles destroyed through them
+type Handles = BTreeMap<u64, BTreeMap<(vk::ObjectType, u64), bool>>;
+
+static HANDLES: Mutex<Handles> = Mutex::new(BTreeMap::new());
+static PRINT_ONLY: AtomicBool = AtomicBool::new(false);
+
+fn handles() -> std::sync::MutexGuard<'static, Handles> {
+    HANDLES.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+fn parent_key<H: Handle>(parent: u64) -> u64 {
+    match H::TYPE {
+        vk::ObjectType::DEVICE | vk::ObjectType::INSTANCE => 0,
+        _ => parent,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OnViolation {
+    #[default]
+    Panic,
+    // prints the violation to stderr and skips the destruction
+    Print,
+}
+
+pub fn set_on_violation(on_violation: OnViolation) {
+    PRINT_ONLY.store(on_violation == OnViolation::Print, Ordering::Relaxed);
+}
+
+// reports a handle created through `parent`, a device or instance, or the device or instance
+// itself, so that its destructions are checked
+//
+// returns the handle so that creation calls can be wrapped
+pub fn created<P: Handle, H: Handle + Copy>(parent: P, handle: H) -> H {
+    let key = parent_key::<H>(parent.as_raw());
+    handles().entry(key).or_default().insert((H::TYPE, handle.as_raw()), false);
+    handle
+}
+
+// returns whether the destruction is valid and must go through
+pub(crate) fn check_destroy<P: Handle, H: Handle>(parent: P, handle: H) -> bool {
+    let (parent, handle) = (parent.as_raw(), handle.as_raw());
+    let violation = if handle == 0 {
+        format!("ash_destructor: a null {:?} handle was destroyed", H::TYPE)
+    } else {
+        let key = parent_key::<H>(parent);
+        let mut handles = handles();
+        if key == 0 {
+            // the raw values of children may be reused by the next device or instance
+            handles.remove(&parent);
+        }
+        match handles.get_mut(&key).and_then(|handles| handles.get_mut(&(H::TYPE, handle))) {
+            Some(destroyed) if *destroyed => format!(
+                "ash_destructor: {:?} 0x{:x} was destroyed twice through 0x{:x}",
+                H::TYPE,
+                handle,
+                parent
+            ),
+            Some(destroyed) => {
+                *destroyed = true;
+                return true;
+            }
+            // handles that weren't reported can't be checked
+            None => return true,
+        }
+    };
+
+    if PRINT_ONLY.load(Ordering::Relaxed) {
+        eprintln!("{violation}");
+        false
+    } else {
+        panic!("{violation}");
+    }
+}
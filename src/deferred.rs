@@ -0,0 +1,92 @@
+use ash::vk;
+
+use crate::DeviceDestroyable;
+
+// destroys values once the GPU work that may still be using them has completed
+//
+// each value is tagged with the frame index or timeline semaphore value that signals the
+// completion of the last submission using it. Values that are still pending when the destroyer
+// is dropped are dropped without being destroyed, call flush_all before destroying the device.
+// values must be Send so that the destroyer can be handed to the thread collecting them.
+#[derive(Default)]
+pub struct DeferredDestroyer {
+    pending: Vec<(u64, Box<dyn DeviceDestroyable + Send>)>,
+}
+
+impl DeferredDestroyer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // `value` will be destroyed once `completion_value` has been reached
+    pub fn push(&mut self, completion_value: u64, value: impl DeviceDestroyable + Send + 'static) {
+        self.pending.push((completion_value, Box::new(value)));
+    }
+
+    pub fn push_boxed(&mut self, completion_value: u64, value: Box<dyn DeviceDestroyable + Send>) {
+        self.pending.push((completion_value, value));
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// # Safety
+    /// Every value must have been created from `device`, and the GPU work tagged with a value
+    /// lower or equal to `completed_value` must have completed.
+    pub unsafe fn collect(&mut self, device: &ash::Device, completed_value: u64) -> usize {
+        self.collect_alloc(device, completed_value, None)
+    }
+
+    /// # Safety
+    /// See [`DeferredDestroyer::collect`], `allocation_callbacks` must be compatible with the ones
+    /// every value was created with.
+    pub unsafe fn collect_alloc(
+        &mut self,
+        device: &ash::Device,
+        completed_value: u64,
+        allocation_callbacks: Option<&vk::AllocationCallbacks>,
+    ) -> usize {
+        let (ready, pending) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition::<Vec<_>, _>(|(value, _)| *value <= completed_value);
+        self.pending = pending;
+        // values are destroyed in reverse push order, like the fields of a derived struct
+        for (_, value) in ready.iter().rev() {
+            value.destroy_self_alloc(device, allocation_callbacks);
+        }
+        ready.len()
+    }
+
+    /// # Safety
+    /// Every value must have been created from `device` and all of the GPU work using them must
+    /// have completed, e.g. after `device_wait_idle`.
+    pub unsafe fn flush_all(&mut self, device: &ash::Device) {
+        self.flush_all_alloc(device, None);
+    }
+
+    /// # Safety
+    /// See [`DeferredDestroyer::flush_all`], `allocation_callbacks` must be compatible with the
+    /// ones every value was created with.
+    pub unsafe fn flush_all_alloc(
+        &mut self,
+        device: &ash::Device,
+        allocation_callbacks: Option<&vk::AllocationCallbacks>,
+    ) {
+        while let Some((_, value)) = self.pending.pop() {
+            value.destroy_self_alloc(device, allocation_callbacks);
+        }
+    }
+}
+
+impl std::fmt::Debug for DeferredDestroyer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeferredDestroyer")
+            .field("pending", &self.pending.iter().map(|(value, _)| value).collect::<Vec<_>>())
+            .finish()
+    }
+}
@@ -0,0 +1,258 @@
+#[cfg(any(
+    feature = "khr-video-queue",
+    feature = "khr-descriptor-update-template",
+    feature = "ext-opacity-micromap",
+    feature = "ext-validation-cache",
+    feature = "nv-device-generated-commands",
+    feature = "nv-optical-flow",
+    feature = "nvx-binary-import",
+    feature = "fuchsia-buffer-collection",
+))]
+use ash::RawPtr;
+#[cfg(any(
+    feature = "khr-swapchain",
+    feature = "khr-acceleration-structure",
+    feature = "khr-deferred-host-operations",
+    feature = "khr-video-queue",
+    feature = "khr-descriptor-update-template",
+    feature = "khr-sampler-ycbcr-conversion",
+    feature = "ext-private-data",
+    feature = "ext-opacity-micromap",
+    feature = "ext-shader-object",
+    feature = "ext-validation-cache",
+    feature = "nv-ray-tracing",
+    feature = "nv-device-generated-commands",
+    feature = "nv-optical-flow",
+    feature = "nv-cuda-kernel-launch",
+    feature = "nvx-binary-import",
+    feature = "fuchsia-buffer-collection",
+    feature = "intel-performance-query",
+))]
+use {
+    crate::{hooks::should_destroy, Alloc, ExtDeviceDestroyable},
+    ash::vk,
+};
+
+#[cfg(feature = "khr-swapchain")]
+impl ExtDeviceDestroyable<ash::khr::swapchain::Device> for vk::SwapchainKHR {
+    unsafe fn destroy_self_alloc(&self, loader: &ash::khr::swapchain::Device, allocation_callbacks: Alloc) {
+        if should_destroy(loader.device(), *self) {
+            loader.destroy_swapchain(*self, allocation_callbacks);
+        }
+    }
+}
+
+#[cfg(feature = "khr-acceleration-structure")]
+impl ExtDeviceDestroyable<ash::khr::acceleration_structure::Device> for vk::AccelerationStructureKHR {
+    unsafe fn destroy_self_alloc(
+        &self,
+        loader: &ash::khr::acceleration_structure::Device,
+        allocation_callbacks: Alloc,
+    ) {
+        if should_destroy(loader.device(), *self) {
+            loader.destroy_acceleration_structure(*self, allocation_callbacks);
+        }
+    }
+}
+
+#[cfg(feature = "khr-deferred-host-operations")]
+impl ExtDeviceDestroyable<ash::khr::deferred_host_operations::Device> for vk::DeferredOperationKHR {
+    unsafe fn destroy_self_alloc(
+        &self,
+        loader: &ash::khr::deferred_host_operations::Device,
+        allocation_callbacks: Alloc,
+    ) {
+        if should_destroy(loader.device(), *self) {
+            loader.destroy_deferred_operation(*self, allocation_callbacks);
+        }
+    }
+}
+
+#[cfg(feature = "khr-video-queue")]
+impl ExtDeviceDestroyable<ash::khr::video_queue::Device> for vk::VideoSessionKHR {
+    unsafe fn destroy_self_alloc(&self, loader: &ash::khr::video_queue::Device, allocation_callbacks: Alloc) {
+        if should_destroy(loader.device(), *self) {
+            (loader.fp().destroy_video_session_khr)(loader.device(), *self, allocation_callbacks.as_raw_ptr());
+        }
+    }
+}
+
+#[cfg(feature = "khr-video-queue")]
+impl ExtDeviceDestroyable<ash::khr::video_queue::Device> for vk::VideoSessionParametersKHR {
+    unsafe fn destroy_self_alloc(&self, loader: &ash::khr::video_queue::Device, allocation_callbacks: Alloc) {
+        if should_destroy(loader.device(), *self) {
+            (loader.fp().destroy_video_session_parameters_khr)(
+                loader.device(),
+                *self,
+                allocation_callbacks.as_raw_ptr(),
+            );
+        }
+    }
+}
+
+// promoted to core in Vulkan 1.1, for devices that only expose the extension
+#[cfg(feature = "khr-descriptor-update-template")]
+impl ExtDeviceDestroyable<ash::khr::descriptor_update_template::Device> for vk::DescriptorUpdateTemplate {
+    unsafe fn destroy_self_alloc(
+        &self,
+        loader: &ash::khr::descriptor_update_template::Device,
+        allocation_callbacks: Alloc,
+    ) {
+        if should_destroy(loader.device(), *self) {
+            (loader.fp().destroy_descriptor_update_template_khr)(
+                loader.device(),
+                *self,
+                allocation_callbacks.as_raw_ptr(),
+            );
+        }
+    }
+}
+
+// promoted to core in Vulkan 1.1, for devices that only expose the extension
+#[cfg(feature = "khr-sampler-ycbcr-conversion")]
+impl ExtDeviceDestroyable<ash::khr::sampler_ycbcr_conversion::Device> for vk::SamplerYcbcrConversion {
+    unsafe fn destroy_self_alloc(
+        &self,
+        loader: &ash::khr::sampler_ycbcr_conversion::Device,
+        allocation_callbacks: Alloc,
+    ) {
+        if should_destroy(loader.device(), *self) {
+            loader.destroy_sampler_ycbcr_conversion(*self, allocation_callbacks);
+        }
+    }
+}
+
+// promoted to core in Vulkan 1.3, for devices that only expose the extension
+#[cfg(feature = "ext-private-data")]
+impl ExtDeviceDestroyable<ash::ext::private_data::Device> for vk::PrivateDataSlot {
+    unsafe fn destroy_self_alloc(&self, loader: &ash::ext::private_data::Device, allocation_callbacks: Alloc) {
+        if should_destroy(loader.device(), *self) {
+            loader.destroy_private_data_slot(*self, allocation_callbacks);
+        }
+    }
+}
+
+#[cfg(feature = "ext-opacity-micromap")]
+impl ExtDeviceDestroyable<ash::ext::opacity_micromap::Device> for vk::MicromapEXT {
+    unsafe fn destroy_self_alloc(&self, loader: &ash::ext::opacity_micromap::Device, allocation_callbacks: Alloc) {
+        if should_destroy(loader.device(), *self) {
+            (loader.fp().destroy_micromap_ext)(loader.device(), *self, allocation_callbacks.as_raw_ptr());
+        }
+    }
+}
+
+#[cfg(feature = "ext-shader-object")]
+impl ExtDeviceDestroyable<ash::ext::shader_object::Device> for vk::ShaderEXT {
+    unsafe fn destroy_self_alloc(&self, loader: &ash::ext::shader_object::Device, allocation_callbacks: Alloc) {
+        if should_destroy(loader.device(), *self) {
+            loader.destroy_shader(*self, allocation_callbacks);
+        }
+    }
+}
+
+#[cfg(feature = "ext-validation-cache")]
+impl ExtDeviceDestroyable<ash::ext::validation_cache::Device> for vk::ValidationCacheEXT {
+    unsafe fn destroy_self_alloc(&self, loader: &ash::ext::validation_cache::Device, allocation_callbacks: Alloc) {
+        if should_destroy(loader.device(), *self) {
+            (loader.fp().destroy_validation_cache_ext)(loader.device(), *self, allocation_callbacks.as_raw_ptr());
+        }
+    }
+}
+
+#[cfg(feature = "nv-ray-tracing")]
+impl ExtDeviceDestroyable<ash::nv::ray_tracing::Device> for vk::AccelerationStructureNV {
+    unsafe fn destroy_self_alloc(&self, loader: &ash::nv::ray_tracing::Device, allocation_callbacks: Alloc) {
+        if should_destroy(loader.device(), *self) {
+            loader.destroy_acceleration_structure(*self, allocation_callbacks);
+        }
+    }
+}
+
+#[cfg(feature = "nv-device-generated-commands")]
+impl ExtDeviceDestroyable<ash::nv::device_generated_commands::Device> for vk::IndirectCommandsLayoutNV {
+    unsafe fn destroy_self_alloc(
+        &self,
+        loader: &ash::nv::device_generated_commands::Device,
+        allocation_callbacks: Alloc,
+    ) {
+        if should_destroy(loader.device(), *self) {
+            (loader.fp().destroy_indirect_commands_layout_nv)(
+                loader.device(),
+                *self,
+                allocation_callbacks.as_raw_ptr(),
+            );
+        }
+    }
+}
+
+#[cfg(feature = "nv-optical-flow")]
+impl ExtDeviceDestroyable<ash::nv::optical_flow::Device> for vk::OpticalFlowSessionNV {
+    unsafe fn destroy_self_alloc(&self, loader: &ash::nv::optical_flow::Device, allocation_callbacks: Alloc) {
+        if should_destroy(loader.device(), *self) {
+            (loader.fp().destroy_optical_flow_session_nv)(loader.device(), *self, allocation_callbacks.as_raw_ptr());
+        }
+    }
+}
+
+#[cfg(feature = "nv-cuda-kernel-launch")]
+impl ExtDeviceDestroyable<ash::nv::cuda_kernel_launch::Device> for vk::CudaModuleNV {
+    unsafe fn destroy_self_alloc(&self, loader: &ash::nv::cuda_kernel_launch::Device, allocation_callbacks: Alloc) {
+        if should_destroy(loader.device(), *self) {
+            loader.destroy_cuda_module(*self, allocation_callbacks);
+        }
+    }
+}
+
+#[cfg(feature = "nv-cuda-kernel-launch")]
+impl ExtDeviceDestroyable<ash::nv::cuda_kernel_launch::Device> for vk::CudaFunctionNV {
+    unsafe fn destroy_self_alloc(&self, loader: &ash::nv::cuda_kernel_launch::Device, allocation_callbacks: Alloc) {
+        if should_destroy(loader.device(), *self) {
+            loader.destroy_cuda_function(*self, allocation_callbacks);
+        }
+    }
+}
+
+#[cfg(feature = "nvx-binary-import")]
+impl ExtDeviceDestroyable<ash::nvx::binary_import::Device> for vk::CuModuleNVX {
+    unsafe fn destroy_self_alloc(&self, loader: &ash::nvx::binary_import::Device, allocation_callbacks: Alloc) {
+        if should_destroy(loader.device(), *self) {
+            (loader.fp().destroy_cu_module_nvx)(loader.device(), *self, allocation_callbacks.as_raw_ptr());
+        }
+    }
+}
+
+#[cfg(feature = "nvx-binary-import")]
+impl ExtDeviceDestroyable<ash::nvx::binary_import::Device> for vk::CuFunctionNVX {
+    unsafe fn destroy_self_alloc(&self, loader: &ash::nvx::binary_import::Device, allocation_callbacks: Alloc) {
+        if should_destroy(loader.device(), *self) {
+            (loader.fp().destroy_cu_function_nvx)(loader.device(), *self, allocation_callbacks.as_raw_ptr());
+        }
+    }
+}
+
+#[cfg(feature = "fuchsia-buffer-collection")]
+impl ExtDeviceDestroyable<ash::fuchsia::buffer_collection::Device> for vk::BufferCollectionFUCHSIA {
+    unsafe fn destroy_self_alloc(
+        &self,
+        loader: &ash::fuchsia::buffer_collection::Device,
+        allocation_callbacks: Alloc,
+    ) {
+        if should_destroy(loader.device(), *self) {
+            (loader.fp().destroy_buffer_collection_fuchsia)(
+                loader.device(),
+                *self,
+                allocation_callbacks.as_raw_ptr(),
+            );
+        }
+    }
+}
+
+// performance configurations are released without allocation callbacks
+#[cfg(feature = "intel-performance-query")]
+impl ExtDeviceDestroyable<ash::intel::performance_query::Device> for vk::PerformanceConfigurationINTEL {
+    unsafe fn destroy_self_alloc(&self, loader: &ash::intel::performance_query::Device, _: Alloc) {
+        if should_destroy(loader.device(), *self) {
+            let _ = (loader.fp().release_performance_configuration_intel)(loader.device(), *self);
+        }
+    }
+}
@@ -0,0 +1,87 @@
+// tracks which field of which value is being destroyed on the current thread
+//
+// derived impls enter a scope for every field they destroy and slices for every element, without
+// any consumer enabled the scopes compile down to nothing
+
+#[cfg(any(feature = "testing", feature = "tracing", feature = "log"))]
+use std::cell::RefCell;
+
+#[cfg(any(feature = "testing", feature = "tracing", feature = "log"))]
+enum Segment {
+    // `owner` is the name of the type declaring the field, only read by the event emitters
+    Field {
+        #[cfg_attr(not(any(feature = "tracing", feature = "log")), allow(dead_code))]
+        owner: &'static str,
+        name: &'static str,
+    },
+    Index(usize),
+}
+
+#[cfg(any(feature = "testing", feature = "tracing", feature = "log"))]
+thread_local! {
+    static PATH: RefCell<Vec<Segment>> = const { RefCell::new(Vec::new()) };
+}
+
+#[must_use]
+pub struct FieldScope {
+    _private: (),
+}
+
+impl FieldScope {
+    #[inline]
+    pub fn field(_owner: &'static str, _name: &'static str) -> Self {
+        #[cfg(any(feature = "testing", feature = "tracing", feature = "log"))]
+        PATH.with_borrow_mut(|path| {
+            path.push(Segment::Field {
+                owner: _owner,
+                name: _name,
+            })
+        });
+        Self { _private: () }
+    }
+
+    #[inline]
+    pub fn index(_index: usize) -> Self {
+        #[cfg(any(feature = "testing", feature = "tracing", feature = "log"))]
+        PATH.with_borrow_mut(|path| path.push(Segment::Index(_index)));
+        Self { _private: () }
+    }
+}
+
+impl Drop for FieldScope {
+    #[inline]
+    fn drop(&mut self) {
+        #[cfg(any(feature = "testing", feature = "tracing", feature = "log"))]
+        PATH.with_borrow_mut(|path| path.pop());
+    }
+}
+
+// formats the current path as `field.nested[3].handle`
+#[cfg(any(feature = "testing", feature = "tracing", feature = "log"))]
+pub(crate) fn current() -> String {
+    PATH.with_borrow(|path| {
+        let mut formatted = String::new();
+        for segment in path {
+            match segment {
+                Segment::Field { name, .. } if formatted.is_empty() => formatted.push_str(name),
+                Segment::Field { name, .. } => {
+                    formatted.push('.');
+                    formatted.push_str(name);
+                }
+                Segment::Index(index) => formatted.push_str(&format!("[{index}]")),
+            }
+        }
+        formatted
+    })
+}
+
+// the name of the type declaring the outermost field being destroyed, the root of `current`
+#[cfg(any(feature = "tracing", feature = "log"))]
+pub(crate) fn current_owner() -> Option<&'static str> {
+    PATH.with_borrow(|path| {
+        path.iter().find_map(|segment| match segment {
+            Segment::Field { owner, .. } => Some(*owner),
+            Segment::Index(_) => None,
+        })
+    })
+}
@@ -1,4 +1,10 @@
-use crate::{Alloc, DeviceDestroyable, SelfDestroyable};
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque};
+use std::mem::ManuallyDrop;
+use std::sync::{LazyLock, Mutex, OnceLock, PoisonError, RwLock};
+
+use crate::field_path::FieldScope;
+use crate::{Alloc, DeviceDestroyable, ExtDeviceDestroyable, InstanceDestroyable, InstanceLoaders, SelfDestroyable};
 
 impl<T: DeviceDestroyable + ?Sized> DeviceDestroyable for &T {
     unsafe fn destroy_self_alloc(&self, device: &ash::Device, allocation_callbacks: Alloc) {
@@ -12,9 +18,22 @@ impl<T: SelfDestroyable + ?Sized> SelfDestroyable for &T {
     }
 }
 
+impl<T: InstanceDestroyable + ?Sized> InstanceDestroyable for &T {
+    unsafe fn destroy_self_alloc(&self, loaders: &InstanceLoaders, allocation_callbacks: Alloc) {
+        InstanceDestroyable::destroy_self_alloc(*self, loaders, allocation_callbacks);
+    }
+}
+
+impl<L, T: ExtDeviceDestroyable<L> + ?Sized> ExtDeviceDestroyable<L> for &T {
+    unsafe fn destroy_self_alloc(&self, loader: &L, allocation_callbacks: Alloc) {
+        ExtDeviceDestroyable::destroy_self_alloc(*self, loader, allocation_callbacks);
+    }
+}
+
 impl<T: DeviceDestroyable> DeviceDestroyable for [T] {
     unsafe fn destroy_self_alloc(&self, device: &ash::Device, allocation_callbacks: Alloc) {
-        for item in self.iter().rev() {
+        for (i, item) in self.iter().enumerate().rev() {
+            let _index_scope = FieldScope::index(i);
             DeviceDestroyable::destroy_self_alloc(item, device, allocation_callbacks);
         }
     }
@@ -22,25 +41,52 @@ impl<T: DeviceDestroyable> DeviceDestroyable for [T] {
 
 impl<T: SelfDestroyable> SelfDestroyable for [T] {
     unsafe fn destroy_self_alloc(&self, allocation_callbacks: Alloc) {
-        for item in self.iter().rev() {
+        for (i, item) in self.iter().enumerate().rev() {
+            let _index_scope = FieldScope::index(i);
             SelfDestroyable::destroy_self_alloc(item, allocation_callbacks);
         }
     }
 }
 
+impl<T: InstanceDestroyable> InstanceDestroyable for [T] {
+    unsafe fn destroy_self_alloc(&self, loaders: &InstanceLoaders, allocation_callbacks: Alloc) {
+        for (i, item) in self.iter().enumerate().rev() {
+            let _index_scope = FieldScope::index(i);
+            InstanceDestroyable::destroy_self_alloc(item, loaders, allocation_callbacks);
+        }
+    }
+}
+
+impl<L, T: ExtDeviceDestroyable<L>> ExtDeviceDestroyable<L> for [T] {
+    unsafe fn destroy_self_alloc(&self, loader: &L, allocation_callbacks: Alloc) {
+        for (i, item) in self.iter().enumerate().rev() {
+            let _index_scope = FieldScope::index(i);
+            ExtDeviceDestroyable::destroy_self_alloc(item, loader, allocation_callbacks);
+        }
+    }
+}
+
 impl<T: DeviceDestroyable, const S: usize> DeviceDestroyable for [T; S] {
     unsafe fn destroy_self_alloc(&self, device: &ash::Device, allocation_callbacks: Alloc) {
-        for item in self.iter().rev() {
-            DeviceDestroyable::destroy_self_alloc(item, device, allocation_callbacks);
-        }
+        DeviceDestroyable::destroy_self_alloc(self.as_slice(), device, allocation_callbacks);
     }
 }
 
 impl<T: SelfDestroyable, const S: usize> SelfDestroyable for [T; S] {
     unsafe fn destroy_self_alloc(&self, allocation_callbacks: Alloc) {
-        for item in self.iter().rev() {
-            SelfDestroyable::destroy_self_alloc(item, allocation_callbacks);
-        }
+        SelfDestroyable::destroy_self_alloc(self.as_slice(), allocation_callbacks);
+    }
+}
+
+impl<T: InstanceDestroyable, const S: usize> InstanceDestroyable for [T; S] {
+    unsafe fn destroy_self_alloc(&self, loaders: &InstanceLoaders, allocation_callbacks: Alloc) {
+        InstanceDestroyable::destroy_self_alloc(self.as_slice(), loaders, allocation_callbacks);
+    }
+}
+
+impl<L, T: ExtDeviceDestroyable<L>, const S: usize> ExtDeviceDestroyable<L> for [T; S] {
+    unsafe fn destroy_self_alloc(&self, loader: &L, allocation_callbacks: Alloc) {
+        ExtDeviceDestroyable::destroy_self_alloc(self.as_slice(), loader, allocation_callbacks);
     }
 }
 
@@ -56,6 +102,18 @@ impl<T: SelfDestroyable> SelfDestroyable for Vec<T> {
     }
 }
 
+impl<T: InstanceDestroyable> InstanceDestroyable for Vec<T> {
+    unsafe fn destroy_self_alloc(&self, loaders: &InstanceLoaders, allocation_callbacks: Alloc) {
+        InstanceDestroyable::destroy_self_alloc(self.as_slice(), loaders, allocation_callbacks);
+    }
+}
+
+impl<L, T: ExtDeviceDestroyable<L>> ExtDeviceDestroyable<L> for Vec<T> {
+    unsafe fn destroy_self_alloc(&self, loader: &L, allocation_callbacks: Alloc) {
+        ExtDeviceDestroyable::destroy_self_alloc(self.as_slice(), loader, allocation_callbacks);
+    }
+}
+
 impl<T: DeviceDestroyable + ?Sized> DeviceDestroyable for Box<T> {
     unsafe fn destroy_self_alloc(&self, device: &ash::Device, allocation_callbacks: Alloc) {
         DeviceDestroyable::destroy_self_alloc(self.as_ref(), device, allocation_callbacks);
@@ -68,6 +126,18 @@ impl<T: SelfDestroyable + ?Sized> SelfDestroyable for Box<T> {
     }
 }
 
+impl<T: InstanceDestroyable + ?Sized> InstanceDestroyable for Box<T> {
+    unsafe fn destroy_self_alloc(&self, loaders: &InstanceLoaders, allocation_callbacks: Alloc) {
+        InstanceDestroyable::destroy_self_alloc(self.as_ref(), loaders, allocation_callbacks);
+    }
+}
+
+impl<L, T: ExtDeviceDestroyable<L> + ?Sized> ExtDeviceDestroyable<L> for Box<T> {
+    unsafe fn destroy_self_alloc(&self, loader: &L, allocation_callbacks: Alloc) {
+        ExtDeviceDestroyable::destroy_self_alloc(self.as_ref(), loader, allocation_callbacks);
+    }
+}
+
 impl<T: DeviceDestroyable> DeviceDestroyable for Option<T> {
     unsafe fn destroy_self_alloc(&self, device: &ash::Device, allocation_callbacks: Alloc) {
         if let Some(val) = self {
@@ -84,30 +154,255 @@ impl<T: SelfDestroyable> SelfDestroyable for Option<T> {
     }
 }
 
+impl<T: InstanceDestroyable> InstanceDestroyable for Option<T> {
+    unsafe fn destroy_self_alloc(&self, loaders: &InstanceLoaders, allocation_callbacks: Alloc) {
+        if let Some(val) = self {
+            InstanceDestroyable::destroy_self_alloc(val, loaders, allocation_callbacks);
+        }
+    }
+}
+
+impl<L, T: ExtDeviceDestroyable<L>> ExtDeviceDestroyable<L> for Option<T> {
+    unsafe fn destroy_self_alloc(&self, loader: &L, allocation_callbacks: Alloc) {
+        if let Some(val) = self {
+            ExtDeviceDestroyable::destroy_self_alloc(val, loader, allocation_callbacks);
+        }
+    }
+}
+
+// lazy values that were never initialized are skipped instead of being created only to be destroyed
+//
+// `LazyCell::get` and `LazyLock::get` set the minimum Rust version to 1.94, see Cargo.toml
 impl<T: DeviceDestroyable> DeviceDestroyable for std::cell::LazyCell<T> {
     unsafe fn destroy_self_alloc(&self, device: &ash::Device, allocation_callbacks: Alloc) {
-        DeviceDestroyable::destroy_self_alloc(
-            std::cell::LazyCell::force(self),
-            device,
-            allocation_callbacks,
-        );
+        if let Some(val) = std::cell::LazyCell::get(self) {
+            DeviceDestroyable::destroy_self_alloc(val, device, allocation_callbacks);
+        }
     }
 }
 
 impl<T: SelfDestroyable> SelfDestroyable for std::cell::LazyCell<T> {
     unsafe fn destroy_self_alloc(&self, allocation_callbacks: Alloc) {
-        SelfDestroyable::destroy_self_alloc(std::cell::LazyCell::force(self), allocation_callbacks);
+        if let Some(val) = std::cell::LazyCell::get(self) {
+            SelfDestroyable::destroy_self_alloc(val, allocation_callbacks);
+        }
+    }
+}
+
+impl<T: InstanceDestroyable> InstanceDestroyable for std::cell::LazyCell<T> {
+    unsafe fn destroy_self_alloc(&self, loaders: &InstanceLoaders, allocation_callbacks: Alloc) {
+        if let Some(val) = std::cell::LazyCell::get(self) {
+            InstanceDestroyable::destroy_self_alloc(val, loaders, allocation_callbacks);
+        }
+    }
+}
+
+impl<L, T: ExtDeviceDestroyable<L>> ExtDeviceDestroyable<L> for std::cell::LazyCell<T> {
+    unsafe fn destroy_self_alloc(&self, loader: &L, allocation_callbacks: Alloc) {
+        if let Some(val) = std::cell::LazyCell::get(self) {
+            ExtDeviceDestroyable::destroy_self_alloc(val, loader, allocation_callbacks);
+        }
     }
 }
 
 impl<T: DeviceDestroyable> DeviceDestroyable for std::cell::OnceCell<T> {
     unsafe fn destroy_self_alloc(&self, device: &ash::Device, allocation_callbacks: Alloc) {
-        DeviceDestroyable::destroy_self_alloc(&self.get(), device, allocation_callbacks);
+        if let Some(val) = self.get() {
+            DeviceDestroyable::destroy_self_alloc(val, device, allocation_callbacks);
+        }
     }
 }
 
 impl<T: SelfDestroyable> SelfDestroyable for std::cell::OnceCell<T> {
     unsafe fn destroy_self_alloc(&self, allocation_callbacks: Alloc) {
-        SelfDestroyable::destroy_self_alloc(&self.get(), allocation_callbacks);
+        if let Some(val) = self.get() {
+            SelfDestroyable::destroy_self_alloc(val, allocation_callbacks);
+        }
     }
 }
+
+impl<T: InstanceDestroyable> InstanceDestroyable for std::cell::OnceCell<T> {
+    unsafe fn destroy_self_alloc(&self, loaders: &InstanceLoaders, allocation_callbacks: Alloc) {
+        if let Some(val) = self.get() {
+            InstanceDestroyable::destroy_self_alloc(val, loaders, allocation_callbacks);
+        }
+    }
+}
+
+impl<L, T: ExtDeviceDestroyable<L>> ExtDeviceDestroyable<L> for std::cell::OnceCell<T> {
+    unsafe fn destroy_self_alloc(&self, loader: &L, allocation_callbacks: Alloc) {
+        if let Some(val) = self.get() {
+            ExtDeviceDestroyable::destroy_self_alloc(val, loader, allocation_callbacks);
+        }
+    }
+}
+
+// sequences are destroyed back-to-front like slices, `$items` yields the items with their index
+// and is iterated in destruction order
+macro_rules! collection_impls {
+    ($(<$($param:ident),*> $ty:ty => |$this:ident| $items:expr;)*) => {$(
+        impl<T: DeviceDestroyable, $($param),*> DeviceDestroyable for $ty {
+            unsafe fn destroy_self_alloc(&self, device: &ash::Device, allocation_callbacks: Alloc) {
+                let $this = self;
+                for (i, item) in $items {
+                    let _index_scope = i.map(FieldScope::index);
+                    DeviceDestroyable::destroy_self_alloc(item, device, allocation_callbacks);
+                }
+            }
+        }
+
+        impl<T: SelfDestroyable, $($param),*> SelfDestroyable for $ty {
+            unsafe fn destroy_self_alloc(&self, allocation_callbacks: Alloc) {
+                let $this = self;
+                for (i, item) in $items {
+                    let _index_scope = i.map(FieldScope::index);
+                    SelfDestroyable::destroy_self_alloc(item, allocation_callbacks);
+                }
+            }
+        }
+
+        impl<T: InstanceDestroyable, $($param),*> InstanceDestroyable for $ty {
+            unsafe fn destroy_self_alloc(&self, loaders: &InstanceLoaders, allocation_callbacks: Alloc) {
+                let $this = self;
+                for (i, item) in $items {
+                    let _index_scope = i.map(FieldScope::index);
+                    InstanceDestroyable::destroy_self_alloc(item, loaders, allocation_callbacks);
+                }
+            }
+        }
+
+        impl<L, T: ExtDeviceDestroyable<L>, $($param),*> ExtDeviceDestroyable<L> for $ty {
+            unsafe fn destroy_self_alloc(&self, loader: &L, allocation_callbacks: Alloc) {
+                let $this = self;
+                for (i, item) in $items {
+                    let _index_scope = i.map(FieldScope::index);
+                    ExtDeviceDestroyable::destroy_self_alloc(item, loader, allocation_callbacks);
+                }
+            }
+        }
+    )*};
+}
+
+// maps only destroy their values, keys are left untouched
+//
+// sorted collections are destroyed from the largest key to the smallest, hashed collections in
+// their unspecified iteration order, so their items must not depend on each other
+collection_impls! {
+    <> VecDeque<T> => |deque| deque.iter().enumerate().rev().map(|(i, item)| (Some(i), item));
+    <> LinkedList<T> => |list| list.iter().enumerate().rev().map(|(i, item)| (Some(i), item));
+    <K> BTreeMap<K, T> => |map| map.values().rev().map(|item| (None::<usize>, item));
+    <> BTreeSet<T> => |set| set.iter().rev().map(|item| (None::<usize>, item));
+    <K, S> HashMap<K, T, S> => |map| map.values().map(|item| (None::<usize>, item));
+    <S> HashSet<T, S> => |set| set.iter().map(|item| (None::<usize>, item));
+}
+
+// `$inner` borrows the wrapped value from `$this`, a reference to the wrapper, `$bounds` are
+// the bounds on `T` besides the destroy trait
+macro_rules! wrapper_impls {
+    ($(<T ($($bounds:tt)*)> $ty:ty => |$this:ident| $inner:expr;)*) => {$(
+        impl<T: DeviceDestroyable $($bounds)*> DeviceDestroyable for $ty {
+            unsafe fn destroy_self_alloc(&self, device: &ash::Device, allocation_callbacks: Alloc) {
+                let $this = self;
+                DeviceDestroyable::destroy_self_alloc(&$inner, device, allocation_callbacks);
+            }
+        }
+
+        impl<T: SelfDestroyable $($bounds)*> SelfDestroyable for $ty {
+            unsafe fn destroy_self_alloc(&self, allocation_callbacks: Alloc) {
+                let $this = self;
+                SelfDestroyable::destroy_self_alloc(&$inner, allocation_callbacks);
+            }
+        }
+
+        impl<T: InstanceDestroyable $($bounds)*> InstanceDestroyable for $ty {
+            unsafe fn destroy_self_alloc(&self, loaders: &InstanceLoaders, allocation_callbacks: Alloc) {
+                let $this = self;
+                InstanceDestroyable::destroy_self_alloc(&$inner, loaders, allocation_callbacks);
+            }
+        }
+
+        impl<L, T: ExtDeviceDestroyable<L> $($bounds)*> ExtDeviceDestroyable<L> for $ty {
+            unsafe fn destroy_self_alloc(&self, loader: &L, allocation_callbacks: Alloc) {
+                let $this = self;
+                ExtDeviceDestroyable::destroy_self_alloc(&$inner, loader, allocation_callbacks);
+            }
+        }
+    )*};
+}
+
+// shared pointers are left out, as the strong count of a value shared by two fields of a single
+// struct never drops while it is destroyed, see `Shared`
+//
+// locks are still destroyed when poisoned, a `RefCell` that is mutably borrowed panics
+wrapper_impls! {
+    <T (+ Copy)> Cell<T> => |cell| cell.get();
+    <T (+ ?Sized)> RefCell<T> => |cell| &*cell.borrow();
+    <T (+ ?Sized)> Mutex<T> => |mutex| &*mutex.lock().unwrap_or_else(PoisonError::into_inner);
+    <T (+ ?Sized)> RwLock<T> => |lock| &*lock.read().unwrap_or_else(PoisonError::into_inner);
+    <T ()> OnceLock<T> => |lock| lock.get();
+    <T ()> LazyLock<T> => |lazy| LazyLock::get(lazy);
+    <T (+ ?Sized)> ManuallyDrop<T> => |value| &**value;
+}
+
+// elements are destroyed last to first, like the fields of a derived tuple struct, in a scope
+// named by their index
+macro_rules! tuple_impls {
+    ($(($($T:ident $i:tt),+))+) => {$(
+        tuple_impls!(@reverse [$($T)+] [] $($T $i)+);
+    )+};
+    (@reverse [$($T:ident)+] [$($reversed:tt)*] $next:ident $next_i:tt $($rest:tt)*) => {
+        tuple_impls!(@reverse [$($T)+] [$next $next_i $($reversed)*] $($rest)*);
+    };
+    (@reverse [$($T:ident)+] [$($_reversed:ident $i:tt)+]) => {
+        impl<$($T: DeviceDestroyable),+> DeviceDestroyable for ($($T,)+) {
+            unsafe fn destroy_self_alloc(&self, device: &ash::Device, allocation_callbacks: Alloc) {
+                $({
+                    let _index_scope = FieldScope::index($i);
+                    DeviceDestroyable::destroy_self_alloc(&self.$i, device, allocation_callbacks);
+                })+
+            }
+        }
+
+        impl<$($T: SelfDestroyable),+> SelfDestroyable for ($($T,)+) {
+            unsafe fn destroy_self_alloc(&self, allocation_callbacks: Alloc) {
+                $({
+                    let _index_scope = FieldScope::index($i);
+                    SelfDestroyable::destroy_self_alloc(&self.$i, allocation_callbacks);
+                })+
+            }
+        }
+
+        impl<$($T: InstanceDestroyable),+> InstanceDestroyable for ($($T,)+) {
+            unsafe fn destroy_self_alloc(&self, loaders: &InstanceLoaders, allocation_callbacks: Alloc) {
+                $({
+                    let _index_scope = FieldScope::index($i);
+                    InstanceDestroyable::destroy_self_alloc(&self.$i, loaders, allocation_callbacks);
+                })+
+            }
+        }
+
+        impl<L, $($T: ExtDeviceDestroyable<L>),+> ExtDeviceDestroyable<L> for ($($T,)+) {
+            unsafe fn destroy_self_alloc(&self, loader: &L, allocation_callbacks: Alloc) {
+                $({
+                    let _index_scope = FieldScope::index($i);
+                    ExtDeviceDestroyable::destroy_self_alloc(&self.$i, loader, allocation_callbacks);
+                })+
+            }
+        }
+    };
+}
+
+tuple_impls! {
+    (T0 0)
+    (T0 0, T1 1)
+    (T0 0, T1 1, T2 2)
+    (T0 0, T1 1, T2 2, T3 3)
+    (T0 0, T1 1, T2 2, T3 3, T4 4)
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5)
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6)
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7)
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8)
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9)
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10)
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11)
+}
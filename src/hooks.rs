@@ -0,0 +1,131 @@
+// called by every impl right before a handle is destroyed through `parent`, the device or
+// instance owning it, so that null handles can be skipped and the optional debugging features
+// can observe destructions
+//
+// without any of them enabled the hooks compile down to the null check
+
+use std::cell::Cell;
+
+use ash::vk::Handle;
+
+// what happens to null handles, e.g. fields of a partially initialized struct
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NullHandlePolicy {
+    // null handles are never passed to the driver
+    #[default]
+    Skip,
+    // null handles are destroyed like any other handle, which Vulkan allows, and are reported
+    // by debug_checks when enabled
+    Destroy,
+}
+
+thread_local! {
+    static DESTROY_NULL: Cell<bool> = const { Cell::new(false) };
+}
+
+// applies `policy` to the destructions made by `f` on the current thread, so that other threads
+// and code outside of `f` keep skipping null handles
+//
+// e.g. `with_null_handle_policy(NullHandlePolicy::Destroy, || unsafe { value.destroy_self(&device) })`
+pub fn with_null_handle_policy<R>(policy: NullHandlePolicy, f: impl FnOnce() -> R) -> R {
+    // restores the previous policy even if `f` panics
+    struct Restore(bool);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            DESTROY_NULL.set(self.0);
+        }
+    }
+
+    let _restore = Restore(DESTROY_NULL.replace(policy == NullHandlePolicy::Destroy));
+    f()
+}
+
+// reports a newly created handle to the enabled debugging features: leak_tracking reports it until
+// it is destroyed and debug_checks checks its destructions. `parent` is the device or instance the
+// handle was created through, or the handle itself for devices and instances
+//
+// returns the handle so that creation calls can be wrapped, e.g.
+// `track_created(device.handle(), device.create_image(..)?)`
+pub fn track_created<P: Handle + Copy, H: Handle + Copy>(parent: P, handle: H) -> H {
+    #[cfg(feature = "leak-tracking")]
+    crate::leak_tracking::track(parent, handle);
+
+    #[cfg(feature = "debug-checks")]
+    crate::debug_checks::created(parent, handle);
+
+    let _ = parent;
+    handle
+}
+
+// returns whether the handle must actually be destroyed
+#[inline]
+pub(crate) fn should_destroy<P: Handle + Copy, H: Handle + Copy>(parent: P, handle: H) -> bool {
+    if handle.is_null() && !DESTROY_NULL.get() {
+        return false;
+    }
+
+    // emitted first so that the event is there even if a check panics or the driver crashes
+    #[cfg(any(feature = "tracing", feature = "log"))]
+    emit_destroy_event::<H>(parent.as_raw(), handle.as_raw());
+
+    #[cfg(feature = "leak-tracking")]
+    crate::leak_tracking::untrack(parent, handle);
+
+    #[cfg(feature = "debug-checks")]
+    if !crate::debug_checks::check_destroy(parent, handle) {
+        return false;
+    }
+
+    let _ = (parent, handle);
+    true
+}
+
+#[cfg(any(feature = "tracing", feature = "log"))]
+fn emit_destroy_event<H: Handle>(parent: u64, handle: u64) {
+    let owner = crate::field_path::current_owner().unwrap_or_default();
+    let path = crate::field_path::current();
+
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        target: "ash_destructor",
+        object_type = ?H::TYPE,
+        handle = format_args!("0x{handle:x}"),
+        parent = format_args!("0x{parent:x}"),
+        owner,
+        path = path.as_str(),
+        "destroying {:?}",
+        H::TYPE,
+    );
+
+    #[cfg(feature = "log")]
+    if path.is_empty() {
+        log::debug!(
+            target: "ash_destructor",
+            "destroying {:?} 0x{:x} through 0x{:x}",
+            H::TYPE,
+            handle,
+            parent
+        );
+    } else if owner.is_empty() {
+        // the path starts at an element of a collection or tuple destroyed directly
+        log::debug!(
+            target: "ash_destructor",
+            "destroying {:?} 0x{:x} through 0x{:x} at {}",
+            H::TYPE,
+            handle,
+            parent,
+            path
+        );
+    } else {
+        log::debug!(
+            target: "ash_destructor",
+            "destroying {:?} 0x{:x} through 0x{:x} at {}::{}",
+            H::TYPE,
+            handle,
+            parent,
+            owner,
+            path
+        );
+    }
+}
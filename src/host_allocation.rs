@@ -0,0 +1,207 @@
+use std::{
+    alloc::Layout,
+    collections::BTreeMap,
+    ffi::c_void,
+    ptr,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use ash::vk;
+
+type AllocFn = dyn Fn(Layout) -> *mut u8 + Send + Sync;
+type FreeFn = dyn Fn(*mut u8, Layout) + Send + Sync;
+
+// builds allocation callbacks counting the live host allocations of the driver, to check that
+// destroying objects created with them returns all the memory they allocated
+//
+// the driver may call the callbacks from any thread, so allocations are tracked behind a mutex.
+// allocations still live when the tracker is dropped are leaked, as the driver may still use them
+pub struct HostAllocationTracker {
+    // boxed so that the user data pointer given to the driver stays valid when the tracker moves
+    state: Box<TrackerState>,
+}
+
+struct TrackerState {
+    alloc: Box<AllocFn>,
+    free: Box<FreeFn>,
+    // address of every live allocation
+    live: Mutex<BTreeMap<usize, (Layout, vk::SystemAllocationScope)>>,
+}
+
+impl TrackerState {
+    fn live(&self) -> MutexGuard<'_, BTreeMap<usize, (Layout, vk::SystemAllocationScope)>> {
+        self.live.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn allocate(&self, size: usize, alignment: usize, scope: vk::SystemAllocationScope) -> *mut u8 {
+        let Ok(layout) = Layout::from_size_align(size, alignment) else {
+            return ptr::null_mut();
+        };
+        if size == 0 {
+            return ptr::null_mut();
+        }
+        let memory = (self.alloc)(layout);
+        if !memory.is_null() {
+            self.live().insert(memory as usize, (layout, scope));
+        }
+        memory
+    }
+
+    fn free(&self, memory: *mut u8) {
+        if let Some((layout, _)) = self.live().remove(&(memory as usize)) {
+            (self.free)(memory, layout);
+        }
+    }
+}
+
+impl HostAllocationTracker {
+    // allocates with the global allocator
+    pub fn new() -> Self {
+        // the layout is never zero-sized, see TrackerState::allocate
+        Self::with_allocator(
+            |layout| unsafe { std::alloc::alloc(layout) },
+            |memory, layout| unsafe { std::alloc::dealloc(memory, layout) },
+        )
+    }
+
+    // `alloc` must return memory valid for the layout or null, `free` receives the layout the
+    // memory was allocated with. reallocations are made of an allocation, a copy and a free
+    pub fn with_allocator(
+        alloc: impl Fn(Layout) -> *mut u8 + Send + Sync + 'static,
+        free: impl Fn(*mut u8, Layout) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            state: Box::new(TrackerState {
+                alloc: Box::new(alloc),
+                free: Box::new(free),
+                live: Mutex::new(BTreeMap::new()),
+            }),
+        }
+    }
+
+    // callbacks to create objects with and destroy them with afterwards, they borrow the tracker
+    // so that it outlives the calls using them
+    pub fn callbacks(&self) -> vk::AllocationCallbacks<'_> {
+        vk::AllocationCallbacks::default()
+            .user_data(ptr::from_ref::<TrackerState>(&self.state).cast_mut().cast())
+            .pfn_allocation(Some(allocation))
+            .pfn_reallocation(Some(reallocation))
+            .pfn_free(Some(free))
+    }
+
+    pub fn live_allocations(&self, scope: vk::SystemAllocationScope) -> usize {
+        self.state.live().values().filter(|(_, allocation_scope)| *allocation_scope == scope).count()
+    }
+
+    pub fn total_live_allocations(&self) -> usize {
+        self.state.live().len()
+    }
+
+    pub fn live_bytes(&self) -> usize {
+        self.state.live().values().map(|(layout, _)| layout.size()).sum()
+    }
+}
+
+impl Default for HostAllocationTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for HostAllocationTracker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HostAllocationTracker")
+            .field("live_allocations", &self.total_live_allocations())
+            .field("live_bytes", &self.live_bytes())
+            .finish()
+    }
+}
+
+unsafe fn state<'a>(user_data: *mut c_void) -> &'a TrackerState {
+    &*user_data.cast::<TrackerState>()
+}
+
+unsafe extern "system" fn allocation(
+    user_data: *mut c_void,
+    size: usize,
+    alignment: usize,
+    scope: vk::SystemAllocationScope,
+) -> *mut c_void {
+    state(user_data).allocate(size, alignment, scope).cast()
+}
+
+// follows vkReallocationFunction: null behaves like an allocation, a size of 0 like a free, and
+// the original memory is left untouched when the new allocation fails
+unsafe extern "system" fn reallocation(
+    user_data: *mut c_void,
+    original: *mut c_void,
+    size: usize,
+    alignment: usize,
+    scope: vk::SystemAllocationScope,
+) -> *mut c_void {
+    let state = state(user_data);
+    if original.is_null() {
+        return state.allocate(size, alignment, scope).cast();
+    }
+    if size == 0 {
+        state.free(original.cast());
+        return ptr::null_mut();
+    }
+    let Some((original_layout, _)) = state.live().get(&(original as usize)).copied() else {
+        return ptr::null_mut();
+    };
+    let memory = state.allocate(size, alignment, scope);
+    if !memory.is_null() {
+        ptr::copy_nonoverlapping(original.cast::<u8>(), memory, original_layout.size().min(size));
+        state.free(original.cast());
+    }
+    memory.cast()
+}
+
+unsafe extern "system" fn free(user_data: *mut c_void, memory: *mut c_void) {
+    if !memory.is_null() {
+        state(user_data).free(memory.cast());
+    }
+}
+
+// a field holding the allocation callbacks other fields are destroyed with, see
+// #[destroy(alloc = self.field)]
+pub trait AsAllocationCallbacks {
+    fn as_allocation_callbacks(&self) -> Option<vk::AllocationCallbacks<'_>>;
+}
+
+impl AsAllocationCallbacks for vk::AllocationCallbacks<'_> {
+    fn as_allocation_callbacks(&self) -> Option<vk::AllocationCallbacks<'_>> {
+        Some(*self)
+    }
+}
+
+impl<T: AsAllocationCallbacks> AsAllocationCallbacks for Option<T> {
+    fn as_allocation_callbacks(&self) -> Option<vk::AllocationCallbacks<'_>> {
+        self.as_ref().and_then(AsAllocationCallbacks::as_allocation_callbacks)
+    }
+}
+
+impl<T: AsAllocationCallbacks + ?Sized> AsAllocationCallbacks for &T {
+    fn as_allocation_callbacks(&self) -> Option<vk::AllocationCallbacks<'_>> {
+        (**self).as_allocation_callbacks()
+    }
+}
+
+impl<T: AsAllocationCallbacks + ?Sized> AsAllocationCallbacks for Box<T> {
+    fn as_allocation_callbacks(&self) -> Option<vk::AllocationCallbacks<'_>> {
+        self.as_ref().as_allocation_callbacks()
+    }
+}
+
+impl<T: AsAllocationCallbacks + ?Sized> AsAllocationCallbacks for std::sync::Arc<T> {
+    fn as_allocation_callbacks(&self) -> Option<vk::AllocationCallbacks<'_>> {
+        self.as_ref().as_allocation_callbacks()
+    }
+}
+
+impl AsAllocationCallbacks for HostAllocationTracker {
+    fn as_allocation_callbacks(&self) -> Option<vk::AllocationCallbacks<'_>> {
+        Some(self.callbacks())
+    }
+}
@@ -0,0 +1,29 @@
+use ash::vk;
+
+use crate::{hooks::should_destroy, Alloc, InstanceDestroyable, InstanceLoaders};
+
+impl InstanceDestroyable for vk::SurfaceKHR {
+    unsafe fn destroy_self_alloc(&self, loaders: &InstanceLoaders, allocation_callbacks: Alloc) {
+        if should_destroy(loaders.surface.instance(), *self) {
+            loaders.surface.destroy_surface(*self, allocation_callbacks);
+        }
+    }
+}
+
+impl InstanceDestroyable for vk::DebugUtilsMessengerEXT {
+    unsafe fn destroy_self_alloc(&self, loaders: &InstanceLoaders, allocation_callbacks: Alloc) {
+        if should_destroy(loaders.debug_utils.instance(), *self) {
+            loaders.debug_utils.destroy_debug_utils_messenger(*self, allocation_callbacks);
+        }
+    }
+}
+
+impl InstanceDestroyable for vk::DebugReportCallbackEXT {
+    // VK_EXT_debug_report is deprecated in favor of VK_EXT_debug_utils, but callbacks created with it still need to be destroyed
+    #[allow(deprecated)]
+    unsafe fn destroy_self_alloc(&self, loaders: &InstanceLoaders, allocation_callbacks: Alloc) {
+        if should_destroy(loaders.debug_report.instance(), *self) {
+            loaders.debug_report.destroy_debug_report_callback(*self, allocation_callbacks);
+        }
+    }
+}
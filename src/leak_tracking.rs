@@ -0,0 +1,107 @@
+// registry of handles that have been created but not destroyed yet
+//
+// only handles registered with track are reported, every destruction going through the crate
+// unregisters the destroyed handle. Backtraces of the creation sites are captured when enabled
+// through RUST_BACKTRACE or RUST_LIB_BACKTRACE, see std::backtrace::Backtrace::capture.
+// crate::track_created also reports the handle to debug_checks when it is enabled.
+//
+// handles are tracked per parent, the device or instance they were created through, as most
+// handles are only unique per device.
+
+use std::{
+    backtrace::{Backtrace, BacktraceStatus},
+    collections::BTreeMap,
+    fmt::Write,
+    sync::Mutex,
+};
+
+use ash::vk::{self, Handle};
+
+// indexed by (parent, object type, handle)
+type Live = BTreeMap<(u64, vk::ObjectType, u64), Backtrace>;
+
+static LIVE: Mutex<Live> = Mutex::new(BTreeMap::new());
+
+fn live() -> std::sync::MutexGuard<'static, Live> {
+    LIVE.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+// registers a newly created handle, which is reported as leaked until it is destroyed. `parent` is
+// the device or instance the handle was created through, or the handle itself for devices and
+// instances
+//
+// returns the handle so that creation calls can be wrapped, e.g.
+// `track(device.handle(), device.create_image(..)?)`
+pub fn track<P: Handle, H: Handle + Copy>(parent: P, handle: H) -> H {
+    live().insert((parent.as_raw(), H::TYPE, handle.as_raw()), Backtrace::capture());
+    handle
+}
+
+// unregisters a handle destroyed without going through the crate, returns whether it was tracked
+pub fn untrack<P: Handle, H: Handle>(parent: P, handle: H) -> bool {
+    live().remove(&(parent.as_raw(), H::TYPE, handle.as_raw())).is_some()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeakedHandle {
+    // the device or instance the handle was created through
+    pub parent: u64,
+    pub object_type: vk::ObjectType,
+    pub handle: u64,
+    // where the handle was tracked, if backtraces are enabled
+    pub backtrace: Option<String>,
+}
+
+// every tracked handle that hasn't been destroyed yet
+pub fn leaked() -> Vec<LeakedHandle> {
+    live()
+        .iter()
+        .map(|((parent, object_type, handle), backtrace)| LeakedHandle {
+            parent: *parent,
+            object_type: *object_type,
+            handle: *handle,
+            backtrace: (backtrace.status() == BacktraceStatus::Captured).then(|| backtrace.to_string()),
+        })
+        .collect()
+}
+
+// a human readable list of the leaked handles, None if nothing leaked
+pub fn report() -> Option<String> {
+    let leaked = leaked();
+    if leaked.is_empty() {
+        return None;
+    }
+
+    let mut report = format!("{} Vulkan handle(s) were never destroyed:\n", leaked.len());
+    for leak in leaked {
+        let _ = writeln!(
+            report,
+            "  {:?} 0x{:x} created through 0x{:x}",
+            leak.object_type, leak.handle, leak.parent
+        );
+        if let Some(backtrace) = leak.backtrace {
+            for line in backtrace.lines() {
+                let _ = writeln!(report, "      {line}");
+            }
+        }
+    }
+    Some(report)
+}
+
+// prints the report to stderr when dropped, keep it alive until the end of main
+#[must_use = "the report is printed when the guard is dropped"]
+pub struct ReportAtExit {
+    _private: (),
+}
+
+pub fn report_at_exit() -> ReportAtExit {
+    ReportAtExit { _private: () }
+}
+
+impl Drop for ReportAtExit {
+    fn drop(&mut self) {
+        if let Some(report) = report() {
+            eprint!("{report}");
+        }
+    }
+}
@@ -1,26 +1,261 @@
+#[cfg(feature = "debug-checks")]
+pub mod debug_checks;
+mod deferred;
 mod device_impls;
+mod ext_device_impls;
+mod field_path;
 mod generic_impls;
+mod hooks;
+mod host_allocation;
+mod instance_impls;
+#[cfg(feature = "leak-tracking")]
+pub mod leak_tracking;
+mod owned;
+mod pooled;
 mod self_impls;
+mod set_null;
+mod shared;
+mod take;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod wait;
 
 use ash::vk;
-pub use ash_destructor_derive::DeviceDestroyable;
+pub use ash_destructor_derive::{DeviceDestroyable, InstanceDestroyable, SelfDestroyable, SetNull};
+pub use deferred::DeferredDestroyer;
+pub use hooks::{track_created, with_null_handle_policy, NullHandlePolicy};
+pub use host_allocation::{AsAllocationCallbacks, HostAllocationTracker};
+pub use owned::Owned;
+pub use pooled::{PoolAllocated, PooledCommandBuffers, PooledDescriptorSets};
+pub use set_null::SetNull;
+pub use shared::Shared;
+pub use take::DestroyTake;
+pub use wait::{
+    destroy_after_fence, destroy_after_fence_alloc, destroy_after_timeline, destroy_after_timeline_alloc, try_destroy_all,
+    try_destroy_all_after_queues, try_destroy_all_after_queues_alloc, try_destroy_all_alloc, DestroyError, DestroyStep,
+};
 
-type Alloc<'a> = Option<&'a vk::AllocationCallbacks<'a>>;
+// used by the derive macros, not part of the public API
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::field_path::FieldScope;
+    pub use crate::pooled::free_pooled;
+}
+
+// the allocation callbacks taken by the destroy functions, for implementations outside the crate
+pub type Alloc<'a> = Option<&'a vk::AllocationCallbacks<'a>>;
 
 // can destroy itself using a device
 pub trait DeviceDestroyable {
+    /// # Safety
+    /// `self` must have been created from `device`, must not be in use by the device anymore and
+    /// `allocation_callbacks` must be compatible with the ones it was created with.
     unsafe fn destroy_self_alloc(&self, device: &ash::Device, allocation_callbacks: Option<&vk::AllocationCallbacks>);
 
+    /// # Safety
+    /// See [`DeviceDestroyable::destroy_self_alloc`].
     unsafe fn destroy_self(&self, device: &ash::Device) {
         DeviceDestroyable::destroy_self_alloc(self, device, None);
     }
+
+    // consumes `self` so that the destroyed handles cannot be used again
+    /// # Safety
+    /// See [`DeviceDestroyable::destroy_self_alloc`].
+    unsafe fn destroy_alloc(self, device: &ash::Device, allocation_callbacks: Option<&vk::AllocationCallbacks>)
+    where
+        Self: Sized,
+    {
+        DeviceDestroyable::destroy_self_alloc(&self, device, allocation_callbacks);
+    }
+
+    /// # Safety
+    /// See [`DeviceDestroyable::destroy_self_alloc`].
+    unsafe fn destroy(self, device: &ash::Device)
+    where
+        Self: Sized,
+    {
+        DeviceDestroyable::destroy_alloc(self, device, None);
+    }
+
+    // resets the destroyed handles to null, so that destroying `self` again does nothing
+    /// # Safety
+    /// See [`DeviceDestroyable::destroy_self_alloc`].
+    unsafe fn destroy_and_null_alloc(&mut self, device: &ash::Device, allocation_callbacks: Option<&vk::AllocationCallbacks>)
+    where
+        Self: SetNull + Sized,
+    {
+        DeviceDestroyable::destroy_self_alloc(self, device, allocation_callbacks);
+        self.set_null();
+    }
+
+    /// # Safety
+    /// See [`DeviceDestroyable::destroy_self_alloc`].
+    unsafe fn destroy_and_null(&mut self, device: &ash::Device)
+    where
+        Self: SetNull + Sized,
+    {
+        DeviceDestroyable::destroy_and_null_alloc(self, device, None);
+    }
 }
 
 // can destroy itself without the need of a device
 pub trait SelfDestroyable: DeviceDestroyable {
+    /// # Safety
+    /// `self` must not be in use anymore, all of its children must have already been destroyed and
+    /// `allocation_callbacks` must be compatible with the ones it was created with.
     unsafe fn destroy_self_alloc(&self, allocation_callbacks: Option<&vk::AllocationCallbacks>);
 
+    /// # Safety
+    /// See [`SelfDestroyable::destroy_self_alloc`].
     unsafe fn destroy_self(&self) {
         SelfDestroyable::destroy_self_alloc(self, None);
     }
+
+    // consumes `self` so that the destroyed handles cannot be used again
+    /// # Safety
+    /// See [`SelfDestroyable::destroy_self_alloc`].
+    unsafe fn destroy_alloc(self, allocation_callbacks: Option<&vk::AllocationCallbacks>)
+    where
+        Self: Sized,
+    {
+        SelfDestroyable::destroy_self_alloc(&self, allocation_callbacks);
+    }
+
+    /// # Safety
+    /// See [`SelfDestroyable::destroy_self_alloc`].
+    unsafe fn destroy(self)
+    where
+        Self: Sized,
+    {
+        SelfDestroyable::destroy_alloc(self, None);
+    }
+}
+
+// instance-level extension loaders needed to destroy instance children
+#[derive(Clone)]
+pub struct InstanceLoaders {
+    pub surface: ash::khr::surface::Instance,
+    pub debug_utils: ash::ext::debug_utils::Instance,
+    pub debug_report: ash::ext::debug_report::Instance,
+}
+
+impl InstanceLoaders {
+    // extensions that were not enabled on the instance are still loaded, but their functions panic when called
+    pub fn new(entry: &ash::Entry, instance: &ash::Instance) -> Self {
+        Self {
+            surface: ash::khr::surface::Instance::new(entry, instance),
+            debug_utils: ash::ext::debug_utils::Instance::new(entry, instance),
+            debug_report: ash::ext::debug_report::Instance::new(entry, instance),
+        }
+    }
+}
+
+// can destroy itself using instance-level loaders
+pub trait InstanceDestroyable {
+    /// # Safety
+    /// `self` must have been created from the instance `loaders` were loaded from, must not be in
+    /// use anymore and `allocation_callbacks` must be compatible with the ones it was created with.
+    unsafe fn destroy_self_alloc(&self, loaders: &InstanceLoaders, allocation_callbacks: Option<&vk::AllocationCallbacks>);
+
+    /// # Safety
+    /// See [`InstanceDestroyable::destroy_self_alloc`].
+    unsafe fn destroy_self(&self, loaders: &InstanceLoaders) {
+        InstanceDestroyable::destroy_self_alloc(self, loaders, None);
+    }
+
+    // consumes `self` so that the destroyed handles cannot be used again
+    /// # Safety
+    /// See [`InstanceDestroyable::destroy_self_alloc`].
+    unsafe fn destroy_alloc(self, loaders: &InstanceLoaders, allocation_callbacks: Option<&vk::AllocationCallbacks>)
+    where
+        Self: Sized,
+    {
+        InstanceDestroyable::destroy_self_alloc(&self, loaders, allocation_callbacks);
+    }
+
+    /// # Safety
+    /// See [`InstanceDestroyable::destroy_self_alloc`].
+    unsafe fn destroy(self, loaders: &InstanceLoaders)
+    where
+        Self: Sized,
+    {
+        InstanceDestroyable::destroy_alloc(self, loaders, None);
+    }
+
+    // resets the destroyed handles to null, so that destroying `self` again does nothing
+    /// # Safety
+    /// See [`InstanceDestroyable::destroy_self_alloc`].
+    unsafe fn destroy_and_null_alloc(
+        &mut self,
+        loaders: &InstanceLoaders,
+        allocation_callbacks: Option<&vk::AllocationCallbacks>,
+    ) where
+        Self: SetNull + Sized,
+    {
+        InstanceDestroyable::destroy_self_alloc(self, loaders, allocation_callbacks);
+        self.set_null();
+    }
+
+    /// # Safety
+    /// See [`InstanceDestroyable::destroy_self_alloc`].
+    unsafe fn destroy_and_null(&mut self, loaders: &InstanceLoaders)
+    where
+        Self: SetNull + Sized,
+    {
+        InstanceDestroyable::destroy_and_null_alloc(self, loaders, None);
+    }
+}
+
+// can destroy itself using the device-level loader `L` of the extension that created it
+pub trait ExtDeviceDestroyable<L> {
+    /// # Safety
+    /// `self` must have been created from the device `loader` was loaded from, must not be in use
+    /// by the device anymore and `allocation_callbacks` must be compatible with the ones it was
+    /// created with.
+    unsafe fn destroy_self_alloc(&self, loader: &L, allocation_callbacks: Option<&vk::AllocationCallbacks>);
+
+    /// # Safety
+    /// See [`ExtDeviceDestroyable::destroy_self_alloc`].
+    unsafe fn destroy_self(&self, loader: &L) {
+        ExtDeviceDestroyable::destroy_self_alloc(self, loader, None);
+    }
+
+    // consumes `self` so that the destroyed handles cannot be used again
+    /// # Safety
+    /// See [`ExtDeviceDestroyable::destroy_self_alloc`].
+    unsafe fn destroy_alloc(self, loader: &L, allocation_callbacks: Option<&vk::AllocationCallbacks>)
+    where
+        Self: Sized,
+    {
+        ExtDeviceDestroyable::destroy_self_alloc(&self, loader, allocation_callbacks);
+    }
+
+    /// # Safety
+    /// See [`ExtDeviceDestroyable::destroy_self_alloc`].
+    unsafe fn destroy(self, loader: &L)
+    where
+        Self: Sized,
+    {
+        ExtDeviceDestroyable::destroy_alloc(self, loader, None);
+    }
+
+    // resets the destroyed handles to null, so that destroying `self` again does nothing
+    /// # Safety
+    /// See [`ExtDeviceDestroyable::destroy_self_alloc`].
+    unsafe fn destroy_and_null_alloc(&mut self, loader: &L, allocation_callbacks: Option<&vk::AllocationCallbacks>)
+    where
+        Self: SetNull + Sized,
+    {
+        ExtDeviceDestroyable::destroy_self_alloc(self, loader, allocation_callbacks);
+        self.set_null();
+    }
+
+    /// # Safety
+    /// See [`ExtDeviceDestroyable::destroy_self_alloc`].
+    unsafe fn destroy_and_null(&mut self, loader: &L)
+    where
+        Self: SetNull + Sized,
+    {
+        ExtDeviceDestroyable::destroy_and_null_alloc(self, loader, None);
+    }
 }
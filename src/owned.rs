@@ -0,0 +1,87 @@
+use std::{mem::ManuallyDrop, ops::Deref};
+
+use ash::vk;
+
+use crate::DeviceDestroyable;
+
+// destroys the wrapped value when dropped
+//
+// the guard keeps a clone of the device's function pointers, not the device itself: the device
+// must stay alive until every guard created from it has been dropped, leaked or unwrapped
+pub struct Owned<T: DeviceDestroyable> {
+    value: ManuallyDrop<T>,
+    device: ash::Device,
+    allocation_callbacks: Option<vk::AllocationCallbacks<'static>>,
+}
+
+impl<T: DeviceDestroyable> Owned<T> {
+    /// # Safety
+    /// `value` must have been created from `device` and must not be destroyed through any other
+    /// path. `device` must not be destroyed before the guard is dropped.
+    pub unsafe fn new(device: &ash::Device, value: T) -> Self {
+        Self {
+            value: ManuallyDrop::new(value),
+            device: device.clone(),
+            allocation_callbacks: None,
+        }
+    }
+
+    /// # Safety
+    /// See [`Owned::new`], `allocation_callbacks` must be compatible with the ones `value` was
+    /// created with and must remain valid until the guard is dropped.
+    pub unsafe fn new_alloc(
+        device: &ash::Device,
+        value: T,
+        allocation_callbacks: vk::AllocationCallbacks<'static>,
+    ) -> Self {
+        Self {
+            value: ManuallyDrop::new(value),
+            device: device.clone(),
+            allocation_callbacks: Some(allocation_callbacks),
+        }
+    }
+
+    pub fn device(this: &Self) -> &ash::Device {
+        &this.device
+    }
+
+    // gives up ownership without destroying the value
+    pub fn into_inner(this: Self) -> T {
+        let mut this = ManuallyDrop::new(this);
+        unsafe {
+            std::ptr::drop_in_place(&mut this.device);
+            ManuallyDrop::take(&mut this.value)
+        }
+    }
+
+    // gives up ownership without destroying the value, which will never be destroyed by the guard
+    pub fn leak<'a>(this: Self) -> &'a mut T
+    where
+        T: 'a,
+    {
+        Box::leak(Box::new(Self::into_inner(this)))
+    }
+}
+
+impl<T: DeviceDestroyable> Deref for Owned<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: DeviceDestroyable + std::fmt::Debug> std::fmt::Debug for Owned<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Owned").field(&*self.value).finish()
+    }
+}
+
+impl<T: DeviceDestroyable> Drop for Owned<T> {
+    fn drop(&mut self) {
+        unsafe {
+            DeviceDestroyable::destroy_self_alloc(&*self.value, &self.device, self.allocation_callbacks.as_ref());
+            ManuallyDrop::drop(&mut self.value);
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use ash::vk;
+
+use crate::{hooks::should_destroy, Alloc, DeviceDestroyable};
+
+// handles that can't be destroyed on their own and are instead freed through the pool they
+// were allocated from
+pub trait PoolAllocated: vk::Handle + Copy {
+    type Pool: vk::Handle + Copy;
+
+    /// # Safety
+    /// Every handle must have been allocated from `pool`, which must have been created from
+    /// `device`, and must not be in use by the device anymore.
+    unsafe fn free(device: &ash::Device, pool: Self::Pool, handles: &[Self]);
+}
+
+impl PoolAllocated for vk::CommandBuffer {
+    type Pool = vk::CommandPool;
+
+    unsafe fn free(device: &ash::Device, pool: vk::CommandPool, handles: &[Self]) {
+        device.free_command_buffers(pool, handles);
+    }
+}
+
+impl PoolAllocated for vk::DescriptorSet {
+    type Pool = vk::DescriptorPool;
+
+    // the pool must have been created with FREE_DESCRIPTOR_SET, the only possible error is
+    // running out of host memory, which leaves nothing to recover
+    unsafe fn free(device: &ash::Device, pool: vk::DescriptorPool, handles: &[Self]) {
+        let _ = device.free_descriptor_sets(pool, handles);
+    }
+}
+
+// frees all handles with a single call, Vulkan doesn't allow freeing an empty list
+#[doc(hidden)]
+pub unsafe fn free_pooled<H: PoolAllocated>(
+    handles: &(impl AsRef<[H]> + ?Sized),
+    pool: &H::Pool,
+    device: &ash::Device,
+) {
+    let handles: Vec<H> = handles
+        .as_ref()
+        .iter()
+        .copied()
+        .filter(|handle| should_destroy(device.handle(), *handle))
+        .collect();
+    if !handles.is_empty() {
+        H::free(device, *pool, &handles);
+    }
+}
+
+// command buffers freed through the pool they were allocated from, the pool itself is not destroyed
+#[derive(Debug, Clone, Default)]
+pub struct PooledCommandBuffers {
+    pub pool: vk::CommandPool,
+    pub buffers: Vec<vk::CommandBuffer>,
+}
+
+impl DeviceDestroyable for PooledCommandBuffers {
+    unsafe fn destroy_self_alloc(&self, device: &ash::Device, _: Alloc) {
+        free_pooled(&self.buffers, &self.pool, device);
+    }
+}
+
+// descriptor sets freed through the pool they were allocated from, the pool itself is not destroyed
+//
+// the pool must have been created with vk::DescriptorPoolCreateFlags::FREE_DESCRIPTOR_SET
+#[derive(Debug, Clone, Default)]
+pub struct PooledDescriptorSets {
+    pub pool: vk::DescriptorPool,
+    pub sets: Vec<vk::DescriptorSet>,
+}
+
+impl DeviceDestroyable for PooledDescriptorSets {
+    unsafe fn destroy_self_alloc(&self, device: &ash::Device, _: Alloc) {
+        free_pooled(&self.sets, &self.pool, device);
+    }
+}
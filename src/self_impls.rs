@@ -1,25 +1,49 @@
-use crate::{Alloc, DeviceDestroyable, SelfDestroyable};
+use crate::{hooks::should_destroy, Alloc, DeviceDestroyable, InstanceDestroyable, InstanceLoaders, SelfDestroyable};
 
 impl DeviceDestroyable for ash::Device {
     unsafe fn destroy_self_alloc(&self, _: &ash::Device, allocation_callbacks: Alloc) {
-        self.destroy_device(allocation_callbacks);
+        if should_destroy(self.handle(), self.handle()) {
+            self.destroy_device(allocation_callbacks);
+        }
     }
 }
 
 impl SelfDestroyable for ash::Device {
     unsafe fn destroy_self_alloc(&self, allocation_callbacks: Alloc) {
-        self.destroy_device(allocation_callbacks);
+        if should_destroy(self.handle(), self.handle()) {
+            self.destroy_device(allocation_callbacks);
+        }
+    }
+}
+
+impl InstanceDestroyable for ash::Device {
+    unsafe fn destroy_self_alloc(&self, _: &InstanceLoaders, allocation_callbacks: Alloc) {
+        if should_destroy(self.handle(), self.handle()) {
+            self.destroy_device(allocation_callbacks);
+        }
     }
 }
 
 impl DeviceDestroyable for ash::Instance {
     unsafe fn destroy_self_alloc(&self, _: &ash::Device, allocation_callbacks: Alloc) {
-        self.destroy_instance(allocation_callbacks);
+        if should_destroy(self.handle(), self.handle()) {
+            self.destroy_instance(allocation_callbacks);
+        }
     }
 }
 
 impl SelfDestroyable for ash::Instance {
     unsafe fn destroy_self_alloc(&self, allocation_callbacks: Alloc) {
-        self.destroy_instance(allocation_callbacks);
+        if should_destroy(self.handle(), self.handle()) {
+            self.destroy_instance(allocation_callbacks);
+        }
+    }
+}
+
+impl InstanceDestroyable for ash::Instance {
+    unsafe fn destroy_self_alloc(&self, _: &InstanceLoaders, allocation_callbacks: Alloc) {
+        if should_destroy(self.handle(), self.handle()) {
+            self.destroy_instance(allocation_callbacks);
+        }
     }
 }
@@ -0,0 +1,198 @@
+use std::cell::{Cell, LazyCell, OnceCell, RefCell};
+use std::collections::{BTreeMap, HashMap, LinkedList, VecDeque};
+use std::mem::ManuallyDrop;
+use std::sync::{LazyLock, Mutex, OnceLock, PoisonError, RwLock};
+
+use ash::vk;
+
+use crate::{PooledCommandBuffers, PooledDescriptorSets, Shared};
+
+// resets every handle destroyed by the value's destroy impl to null, so that destroying it again
+// skips them instead of destroying handles that may have been reused, see
+// DeviceDestroyable::destroy_and_null
+pub trait SetNull {
+    fn set_null(&mut self);
+}
+
+macro_rules! handle_impls {
+    ($($handle:ty),* $(,)?) => {$(
+        impl SetNull for $handle {
+            fn set_null(&mut self) {
+                *self = <$handle>::null();
+            }
+        }
+    )*};
+}
+
+handle_impls! {
+    vk::AccelerationStructureKHR,
+    vk::AccelerationStructureNV,
+    vk::Buffer,
+    vk::BufferCollectionFUCHSIA,
+    vk::BufferView,
+    vk::CommandBuffer,
+    vk::CommandPool,
+    vk::CuFunctionNVX,
+    vk::CuModuleNVX,
+    vk::CudaFunctionNV,
+    vk::CudaModuleNV,
+    vk::DebugReportCallbackEXT,
+    vk::DebugUtilsMessengerEXT,
+    vk::DeferredOperationKHR,
+    vk::DescriptorPool,
+    vk::DescriptorSet,
+    vk::DescriptorSetLayout,
+    vk::DescriptorUpdateTemplate,
+    vk::DeviceMemory,
+    vk::Event,
+    vk::Fence,
+    vk::Framebuffer,
+    vk::Image,
+    vk::ImageView,
+    vk::IndirectCommandsLayoutNV,
+    vk::MicromapEXT,
+    vk::OpticalFlowSessionNV,
+    vk::PerformanceConfigurationINTEL,
+    vk::Pipeline,
+    vk::PipelineCache,
+    vk::PipelineLayout,
+    vk::PrivateDataSlot,
+    vk::QueryPool,
+    vk::RenderPass,
+    vk::Sampler,
+    vk::SamplerYcbcrConversion,
+    vk::Semaphore,
+    vk::ShaderEXT,
+    vk::ShaderModule,
+    vk::SurfaceKHR,
+    vk::SwapchainKHR,
+    vk::ValidationCacheEXT,
+    vk::VideoSessionKHR,
+    vk::VideoSessionParametersKHR,
+}
+
+impl<T: SetNull> SetNull for [T] {
+    fn set_null(&mut self) {
+        self.iter_mut().for_each(SetNull::set_null);
+    }
+}
+
+impl<T: SetNull, const S: usize> SetNull for [T; S] {
+    fn set_null(&mut self) {
+        self.as_mut_slice().set_null();
+    }
+}
+
+impl<T: SetNull> SetNull for Vec<T> {
+    fn set_null(&mut self) {
+        self.as_mut_slice().set_null();
+    }
+}
+
+impl<T: SetNull + ?Sized> SetNull for Box<T> {
+    fn set_null(&mut self) {
+        self.as_mut().set_null();
+    }
+}
+
+impl<T: SetNull> SetNull for Option<T> {
+    fn set_null(&mut self) {
+        if let Some(val) = self {
+            val.set_null();
+        }
+    }
+}
+
+// sets are left out as their items can't be changed in place
+impl<T: SetNull> SetNull for VecDeque<T> {
+    fn set_null(&mut self) {
+        self.iter_mut().for_each(SetNull::set_null);
+    }
+}
+
+impl<T: SetNull> SetNull for LinkedList<T> {
+    fn set_null(&mut self) {
+        self.iter_mut().for_each(SetNull::set_null);
+    }
+}
+
+impl<K, T: SetNull> SetNull for BTreeMap<K, T> {
+    fn set_null(&mut self) {
+        self.values_mut().for_each(SetNull::set_null);
+    }
+}
+
+impl<K, T: SetNull, S> SetNull for HashMap<K, T, S> {
+    fn set_null(&mut self) {
+        self.values_mut().for_each(SetNull::set_null);
+    }
+}
+
+// `$inner` mutably borrows the wrapped value from `$this`, if there is one
+macro_rules! wrapper_impls {
+    ($(<T ($($bounds:tt)*)> $ty:ty => |$this:ident| $inner:expr;)*) => {$(
+        impl<T: SetNull $($bounds)*> SetNull for $ty {
+            fn set_null(&mut self) {
+                let $this = self;
+                if let Some(val) = $inner {
+                    val.set_null();
+                }
+            }
+        }
+    )*};
+}
+
+wrapper_impls! {
+    <T (+ ?Sized)> Cell<T> => |cell| Some(cell.get_mut());
+    <T (+ ?Sized)> RefCell<T> => |cell| Some(cell.get_mut());
+    <T (+ ?Sized)> Mutex<T> => |mutex| Some(mutex.get_mut().unwrap_or_else(PoisonError::into_inner));
+    <T (+ ?Sized)> RwLock<T> => |lock| Some(lock.get_mut().unwrap_or_else(PoisonError::into_inner));
+    <T ()> OnceCell<T> => |cell| cell.get_mut();
+    <T ()> OnceLock<T> => |lock| lock.get_mut();
+    <T ()> LazyCell<T> => |lazy| LazyCell::get_mut(lazy);
+    <T ()> LazyLock<T> => |lazy| LazyLock::get_mut(lazy);
+    <T (+ ?Sized)> ManuallyDrop<T> => |value| Some(&mut **value);
+}
+
+// the share is given up by destroying it, so destroying this owner again already does nothing
+impl<T> SetNull for Shared<T> {
+    fn set_null(&mut self) {}
+}
+
+macro_rules! tuple_impls {
+    ($(($($T:ident $i:tt),+))+) => {$(
+        impl<$($T: SetNull),+> SetNull for ($($T,)+) {
+            fn set_null(&mut self) {
+                $(self.$i.set_null();)+
+            }
+        }
+    )+};
+}
+
+tuple_impls! {
+    (T0 0)
+    (T0 0, T1 1)
+    (T0 0, T1 1, T2 2)
+    (T0 0, T1 1, T2 2, T3 3)
+    (T0 0, T1 1, T2 2, T3 3, T4 4)
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5)
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6)
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7)
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8)
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9)
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10)
+    (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11)
+}
+
+// the pool is not destroyed, only the handles freed through it are reset
+impl SetNull for PooledCommandBuffers {
+    fn set_null(&mut self) {
+        self.buffers.set_null();
+    }
+}
+
+impl SetNull for PooledDescriptorSets {
+    fn set_null(&mut self) {
+        self.sets.set_null();
+    }
+}
@@ -0,0 +1,124 @@
+use std::{
+    ops::Deref,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use crate::{Alloc, DeviceDestroyable, ExtDeviceDestroyable, InstanceDestroyable, InstanceLoaders, SelfDestroyable};
+
+// shares a value between several owners, the value is destroyed by the last owner to be
+// destroyed. dropping an owner without destroying it gives up its share
+//
+// `Rc` and `Arc` can't do this as destroying only borrows them: the strong count of a value shared
+// by two fields of a single struct never drops during its destruction. each clone counts its own
+// share instead, and destroying a clone again does nothing
+pub struct Shared<T> {
+    inner: Arc<SharedInner<T>>,
+    released: AtomicBool,
+}
+
+struct SharedInner<T> {
+    value: T,
+    // clones that have not been destroyed or dropped yet
+    owners: AtomicUsize,
+    destroyed: AtomicBool,
+}
+
+impl<T> Shared<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            inner: Arc::new(SharedInner {
+                value,
+                owners: AtomicUsize::new(1),
+                destroyed: AtomicBool::new(false),
+            }),
+            released: AtomicBool::new(false),
+        }
+    }
+
+    // the number of clones that still have to be destroyed or dropped before the value is destroyed
+    pub fn owners(this: &Self) -> usize {
+        this.inner.owners.load(Ordering::Acquire)
+    }
+
+    pub fn is_destroyed(this: &Self) -> bool {
+        this.inner.destroyed.load(Ordering::Acquire)
+    }
+
+    // gives up the share of `this`, returns whether the value must be destroyed
+    fn release(&self) -> bool {
+        !self.released.swap(true, Ordering::AcqRel)
+            && self.inner.owners.fetch_sub(1, Ordering::AcqRel) == 1
+            && !self.inner.destroyed.swap(true, Ordering::AcqRel)
+    }
+}
+
+impl<T> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        self.inner.owners.fetch_add(1, Ordering::AcqRel);
+        Self {
+            inner: self.inner.clone(),
+            released: AtomicBool::new(false),
+        }
+    }
+}
+
+impl<T> Deref for Shared<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner.value
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Shared<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Shared")
+            .field("value", &self.inner.value)
+            .field("owners", &Self::owners(self))
+            .field("destroyed", &Self::is_destroyed(self))
+            .finish()
+    }
+}
+
+impl<T> Drop for Shared<T> {
+    fn drop(&mut self) {
+        if !*self.released.get_mut() {
+            self.inner.owners.fetch_sub(1, Ordering::AcqRel);
+        }
+    }
+}
+
+impl<T: DeviceDestroyable> DeviceDestroyable for Shared<T> {
+    unsafe fn destroy_self_alloc(&self, device: &ash::Device, allocation_callbacks: Alloc) {
+        if self.release() {
+            DeviceDestroyable::destroy_self_alloc(&self.inner.value, device, allocation_callbacks);
+        }
+    }
+}
+
+impl<T: SelfDestroyable> SelfDestroyable for Shared<T> {
+    unsafe fn destroy_self_alloc(&self, allocation_callbacks: Alloc) {
+        if self.release() {
+            SelfDestroyable::destroy_self_alloc(&self.inner.value, allocation_callbacks);
+        }
+    }
+}
+
+impl<T: InstanceDestroyable> InstanceDestroyable for Shared<T> {
+    unsafe fn destroy_self_alloc(&self, loaders: &InstanceLoaders, allocation_callbacks: Alloc) {
+        if self.release() {
+            InstanceDestroyable::destroy_self_alloc(&self.inner.value, loaders, allocation_callbacks);
+        }
+    }
+}
+
+impl<L, T: ExtDeviceDestroyable<L>> ExtDeviceDestroyable<L> for Shared<T> {
+    unsafe fn destroy_self_alloc(&self, loader: &L, allocation_callbacks: Alloc) {
+        if self.release() {
+            ExtDeviceDestroyable::destroy_self_alloc(&self.inner.value, loader, allocation_callbacks);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use std::cell::OnceCell;
+use std::sync::OnceLock;
+
+use ash::vk;
+
+use crate::field_path::FieldScope;
+use crate::{Alloc, DeviceDestroyable};
+
+// destroys the contents of a container and leaves it empty, so that the destroyed handles
+// cannot be used or destroyed again through it
+pub trait DestroyTake {
+    /// # Safety
+    /// See [`DeviceDestroyable::destroy_self_alloc`], for the contents of the container.
+    unsafe fn destroy_take_alloc(&mut self, device: &ash::Device, allocation_callbacks: Option<&vk::AllocationCallbacks>);
+
+    /// # Safety
+    /// See [`DestroyTake::destroy_take_alloc`].
+    unsafe fn destroy_take(&mut self, device: &ash::Device) {
+        self.destroy_take_alloc(device, None);
+    }
+}
+
+impl<T: DeviceDestroyable> DestroyTake for Option<T> {
+    unsafe fn destroy_take_alloc(&mut self, device: &ash::Device, allocation_callbacks: Alloc) {
+        if let Some(val) = self.take() {
+            val.destroy_self_alloc(device, allocation_callbacks);
+        }
+    }
+}
+
+impl<T: DeviceDestroyable> DestroyTake for OnceCell<T> {
+    unsafe fn destroy_take_alloc(&mut self, device: &ash::Device, allocation_callbacks: Alloc) {
+        if let Some(val) = self.take() {
+            val.destroy_self_alloc(device, allocation_callbacks);
+        }
+    }
+}
+
+impl<T: DeviceDestroyable> DestroyTake for OnceLock<T> {
+    unsafe fn destroy_take_alloc(&mut self, device: &ash::Device, allocation_callbacks: Alloc) {
+        if let Some(val) = self.take() {
+            val.destroy_self_alloc(device, allocation_callbacks);
+        }
+    }
+}
+
+// destroyed back-to-front like slices
+impl<T: DeviceDestroyable> DestroyTake for Vec<T> {
+    unsafe fn destroy_take_alloc(&mut self, device: &ash::Device, allocation_callbacks: Alloc) {
+        for (i, item) in self.drain(..).enumerate().rev() {
+            let _index_scope = FieldScope::index(i);
+            item.destroy_self_alloc(device, allocation_callbacks);
+        }
+    }
+}
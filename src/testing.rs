@@ -0,0 +1,381 @@
+// a fake Vulkan implementation that records destructions instead of talking to a driver
+//
+// only the functions needed to destroy objects and to wait before doing so are implemented,
+// calling any other function through the mock panics
+
+use std::{
+    collections::BTreeMap,
+    ffi::{c_char, c_void, CStr},
+    ops::Deref,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+};
+
+use ash::vk::{self, Handle};
+
+use crate::InstanceLoaders;
+
+mod recorder;
+
+pub use recorder::{entries_to_json, DestructionEntry, DestructionRecorder};
+
+// a single call to a vkDestroy* / vkFree* / vkRelease* function
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DestroyCall {
+    pub object_type: vk::ObjectType,
+    pub handle: u64,
+    pub allocation_callbacks: *const vk::AllocationCallbacks<'static>,
+    // user data of the allocation callbacks, identifies callbacks built on the fly such as the
+    // ones of #[destroy(alloc = self.field)]
+    pub allocation_user_data: *mut c_void,
+}
+
+// the allocation callbacks pointers are only recorded, never dereferenced
+unsafe impl Send for DestroyCall {}
+
+impl DestroyCall {
+    pub fn new<H: Handle>(handle: H, allocation_callbacks: Option<&vk::AllocationCallbacks>) -> Self {
+        Self {
+            object_type: H::TYPE,
+            handle: handle.as_raw(),
+            allocation_callbacks: allocation_callbacks.map_or(std::ptr::null(), |callbacks| {
+                (callbacks as *const vk::AllocationCallbacks).cast()
+            }),
+            allocation_user_data: allocation_callbacks.map_or(std::ptr::null_mut(), |callbacks| callbacks.p_user_data),
+        }
+    }
+}
+
+struct MockState {
+    calls: Vec<DestroyCall>,
+    // returned by vkWaitForFences, vkWaitSemaphores and vkDeviceWaitIdle
+    wait_result: vk::Result,
+    // returned by vkQueueWaitIdle, SUCCESS for queues without a result
+    queue_wait_results: BTreeMap<u64, vk::Result>,
+}
+
+// state of every live mock, indexed by the raw value of its dispatchable handles
+static MOCKS: Mutex<BTreeMap<u64, MockState>> = Mutex::new(BTreeMap::new());
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+fn mocks() -> std::sync::MutexGuard<'static, BTreeMap<u64, MockState>> {
+    // a panicking test must not poison the log of every other test
+    MOCKS.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+fn record(id: u64, call: DestroyCall) {
+    if let Some(state) = mocks().get_mut(&id) {
+        state.calls.push(call);
+    }
+}
+
+fn wait_result(id: u64) -> vk::Result {
+    mocks().get(&id).map_or(vk::Result::SUCCESS, |state| state.wait_result)
+}
+
+// queues aren't created through the mock, so their results are looked up in every mock
+fn queue_wait_result(queue: vk::Queue) -> vk::Result {
+    mocks()
+        .values()
+        .find_map(|state| state.queue_wait_results.get(&queue.as_raw()).copied())
+        .unwrap_or(vk::Result::SUCCESS)
+}
+
+// a mock instance and device that share a single log of destruction calls
+pub struct MockDevice {
+    id: u64,
+    entry: ash::Entry,
+    instance: ash::Instance,
+    device: ash::Device,
+}
+
+impl MockDevice {
+    pub fn new() -> Self {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        mocks().insert(
+            id,
+            MockState {
+                calls: Vec::new(),
+                wait_result: vk::Result::SUCCESS,
+                queue_wait_results: BTreeMap::new(),
+            },
+        );
+
+        let static_fn = ash::StaticFn {
+            get_instance_proc_addr: mock_get_instance_proc_addr,
+        };
+        // the mock functions don't access any state besides the one kept in MOCKS
+        unsafe {
+            let entry = ash::Entry::from_static_fn(static_fn);
+            let instance = ash::Instance::load(entry.static_fn(), vk::Instance::from_raw(id));
+            let device = ash::Device::load(instance.fp_v1_0(), vk::Device::from_raw(id));
+            Self {
+                id,
+                entry,
+                instance,
+                device,
+            }
+        }
+    }
+
+    pub fn entry(&self) -> &ash::Entry {
+        &self.entry
+    }
+
+    pub fn instance(&self) -> &ash::Instance {
+        &self.instance
+    }
+
+    pub fn device(&self) -> &ash::Device {
+        &self.device
+    }
+
+    pub fn instance_loaders(&self) -> InstanceLoaders {
+        InstanceLoaders::new(&self.entry, &self.instance)
+    }
+
+    // every call recorded so far, in call order
+    pub fn calls(&self) -> Vec<DestroyCall> {
+        mocks().get(&self.id).map(|state| state.calls.clone()).unwrap_or_default()
+    }
+
+    // same as calls, but also clears the log
+    pub fn take_calls(&self) -> Vec<DestroyCall> {
+        mocks()
+            .get_mut(&self.id)
+            .map(|state| std::mem::take(&mut state.calls))
+            .unwrap_or_default()
+    }
+
+    // sets the result of every following wait on fences, semaphores or the device being idle,
+    // SUCCESS by default
+    pub fn set_wait_result(&self, result: vk::Result) {
+        if let Some(state) = mocks().get_mut(&self.id) {
+            state.wait_result = result;
+        }
+    }
+
+    // sets the result of every following wait on `queue` being idle, SUCCESS by default
+    pub fn set_queue_wait_result(&self, queue: vk::Queue, result: vk::Result) {
+        if let Some(state) = mocks().get_mut(&self.id) {
+            state.queue_wait_results.insert(queue.as_raw(), result);
+        }
+    }
+}
+
+impl Default for MockDevice {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for MockDevice {
+    type Target = ash::Device;
+
+    fn deref(&self) -> &ash::Device {
+        &self.device
+    }
+}
+
+impl Drop for MockDevice {
+    fn drop(&mut self) {
+        mocks().remove(&self.id);
+    }
+}
+
+macro_rules! mock_destroy_fns {
+    ($($name:literal => $fn_name:ident($parent:ty, $handle:ty)),* $(,)?) => {
+        $(
+            unsafe extern "system" fn $fn_name(
+                parent: $parent,
+                handle: $handle,
+                p_allocator: *const vk::AllocationCallbacks<'_>,
+            ) {
+                record(parent.as_raw(), DestroyCall::new(handle, p_allocator.as_ref()));
+            }
+        )*
+
+        const MOCK_DESTROY_FNS: &[(&CStr, vk::PFN_vkVoidFunction)] = &[
+            $(
+                (
+                    $name,
+                    Some(unsafe {
+                        std::mem::transmute::<
+                            unsafe extern "system" fn($parent, $handle, *const vk::AllocationCallbacks<'_>),
+                            unsafe extern "system" fn(),
+                        >($fn_name)
+                    }),
+                ),
+            )*
+        ];
+    };
+}
+
+mock_destroy_fns! {
+    c"vkDestroySurfaceKHR" => destroy_surface(vk::Instance, vk::SurfaceKHR),
+    c"vkDestroyDebugUtilsMessengerEXT" => destroy_debug_utils_messenger(vk::Instance, vk::DebugUtilsMessengerEXT),
+    c"vkDestroyDebugReportCallbackEXT" => destroy_debug_report_callback(vk::Instance, vk::DebugReportCallbackEXT),
+
+    c"vkDestroyPrivateDataSlot" => destroy_private_data_slot(vk::Device, vk::PrivateDataSlot),
+    c"vkDestroyPrivateDataSlotEXT" => destroy_private_data_slot_ext(vk::Device, vk::PrivateDataSlot),
+    c"vkDestroySamplerYcbcrConversion" => destroy_sampler_ycbcr_conversion(vk::Device, vk::SamplerYcbcrConversion),
+    c"vkDestroySamplerYcbcrConversionKHR" => destroy_sampler_ycbcr_conversion_khr(vk::Device, vk::SamplerYcbcrConversion),
+    c"vkDestroyDescriptorUpdateTemplate" => destroy_descriptor_update_template(vk::Device, vk::DescriptorUpdateTemplate),
+    c"vkDestroyDescriptorUpdateTemplateKHR" => destroy_descriptor_update_template_khr(vk::Device, vk::DescriptorUpdateTemplate),
+    c"vkDestroySampler" => destroy_sampler(vk::Device, vk::Sampler),
+    c"vkDestroyFence" => destroy_fence(vk::Device, vk::Fence),
+    c"vkDestroyEvent" => destroy_event(vk::Device, vk::Event),
+    c"vkDestroyImage" => destroy_image(vk::Device, vk::Image),
+    c"vkDestroyCommandPool" => destroy_command_pool(vk::Device, vk::CommandPool),
+    c"vkDestroyImageView" => destroy_image_view(vk::Device, vk::ImageView),
+    c"vkDestroyRenderPass" => destroy_render_pass(vk::Device, vk::RenderPass),
+    c"vkDestroyFramebuffer" => destroy_framebuffer(vk::Device, vk::Framebuffer),
+    c"vkDestroyPipelineLayout" => destroy_pipeline_layout(vk::Device, vk::PipelineLayout),
+    c"vkDestroyPipelineCache" => destroy_pipeline_cache(vk::Device, vk::PipelineCache),
+    c"vkDestroyBuffer" => destroy_buffer(vk::Device, vk::Buffer),
+    c"vkDestroyShaderModule" => destroy_shader_module(vk::Device, vk::ShaderModule),
+    c"vkDestroyPipeline" => destroy_pipeline(vk::Device, vk::Pipeline),
+    c"vkDestroySemaphore" => destroy_semaphore(vk::Device, vk::Semaphore),
+    c"vkDestroyDescriptorPool" => destroy_descriptor_pool(vk::Device, vk::DescriptorPool),
+    c"vkDestroyQueryPool" => destroy_query_pool(vk::Device, vk::QueryPool),
+    c"vkDestroyDescriptorSetLayout" => destroy_descriptor_set_layout(vk::Device, vk::DescriptorSetLayout),
+    c"vkDestroyBufferView" => destroy_buffer_view(vk::Device, vk::BufferView),
+    c"vkFreeMemory" => free_memory(vk::Device, vk::DeviceMemory),
+
+    c"vkDestroySwapchainKHR" => destroy_swapchain(vk::Device, vk::SwapchainKHR),
+    c"vkDestroyAccelerationStructureKHR" => destroy_acceleration_structure(vk::Device, vk::AccelerationStructureKHR),
+    c"vkDestroyDeferredOperationKHR" => destroy_deferred_operation(vk::Device, vk::DeferredOperationKHR),
+    c"vkDestroyVideoSessionKHR" => destroy_video_session(vk::Device, vk::VideoSessionKHR),
+    c"vkDestroyVideoSessionParametersKHR" => destroy_video_session_parameters(vk::Device, vk::VideoSessionParametersKHR),
+    c"vkDestroyMicromapEXT" => destroy_micromap(vk::Device, vk::MicromapEXT),
+    c"vkDestroyShaderEXT" => destroy_shader(vk::Device, vk::ShaderEXT),
+    c"vkDestroyValidationCacheEXT" => destroy_validation_cache(vk::Device, vk::ValidationCacheEXT),
+    c"vkDestroyAccelerationStructureNV" => destroy_acceleration_structure_nv(vk::Device, vk::AccelerationStructureNV),
+    c"vkDestroyIndirectCommandsLayoutNV" => destroy_indirect_commands_layout(vk::Device, vk::IndirectCommandsLayoutNV),
+    c"vkDestroyOpticalFlowSessionNV" => destroy_optical_flow_session(vk::Device, vk::OpticalFlowSessionNV),
+    c"vkDestroyCudaModuleNV" => destroy_cuda_module(vk::Device, vk::CudaModuleNV),
+    c"vkDestroyCudaFunctionNV" => destroy_cuda_function(vk::Device, vk::CudaFunctionNV),
+    c"vkDestroyCuModuleNVX" => destroy_cu_module(vk::Device, vk::CuModuleNVX),
+    c"vkDestroyCuFunctionNVX" => destroy_cu_function(vk::Device, vk::CuFunctionNVX),
+    c"vkDestroyBufferCollectionFUCHSIA" => destroy_buffer_collection(vk::Device, vk::BufferCollectionFUCHSIA),
+}
+
+unsafe extern "system" fn destroy_instance(instance: vk::Instance, p_allocator: *const vk::AllocationCallbacks<'_>) {
+    record(instance.as_raw(), DestroyCall::new(instance, p_allocator.as_ref()));
+}
+
+unsafe extern "system" fn destroy_device(device: vk::Device, p_allocator: *const vk::AllocationCallbacks<'_>) {
+    record(device.as_raw(), DestroyCall::new(device, p_allocator.as_ref()));
+}
+
+unsafe extern "system" fn release_performance_configuration(
+    device: vk::Device,
+    configuration: vk::PerformanceConfigurationINTEL,
+) -> vk::Result {
+    record(device.as_raw(), DestroyCall::new(configuration, None));
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn free_command_buffers(
+    device: vk::Device,
+    _: vk::CommandPool,
+    command_buffer_count: u32,
+    p_command_buffers: *const vk::CommandBuffer,
+) {
+    for &buffer in std::slice::from_raw_parts(p_command_buffers, command_buffer_count as usize) {
+        record(device.as_raw(), DestroyCall::new(buffer, None));
+    }
+}
+
+unsafe extern "system" fn free_descriptor_sets(
+    device: vk::Device,
+    _: vk::DescriptorPool,
+    descriptor_set_count: u32,
+    p_descriptor_sets: *const vk::DescriptorSet,
+) -> vk::Result {
+    for &set in std::slice::from_raw_parts(p_descriptor_sets, descriptor_set_count as usize) {
+        record(device.as_raw(), DestroyCall::new(set, None));
+    }
+    vk::Result::SUCCESS
+}
+
+unsafe extern "system" fn wait_for_fences(
+    device: vk::Device,
+    _: u32,
+    _: *const vk::Fence,
+    _: vk::Bool32,
+    _: u64,
+) -> vk::Result {
+    wait_result(device.as_raw())
+}
+
+unsafe extern "system" fn wait_semaphores(device: vk::Device, _: *const vk::SemaphoreWaitInfo<'_>, _: u64) -> vk::Result {
+    wait_result(device.as_raw())
+}
+
+unsafe extern "system" fn device_wait_idle(device: vk::Device) -> vk::Result {
+    wait_result(device.as_raw())
+}
+
+unsafe extern "system" fn queue_wait_idle(queue: vk::Queue) -> vk::Result {
+    queue_wait_result(queue)
+}
+
+unsafe extern "system" fn mock_get_device_proc_addr(_: vk::Device, p_name: *const c_char) -> vk::PFN_vkVoidFunction {
+    mock_proc_addr(CStr::from_ptr(p_name))
+}
+
+unsafe extern "system" fn mock_get_instance_proc_addr(
+    _: vk::Instance,
+    p_name: *const c_char,
+) -> vk::PFN_vkVoidFunction {
+    mock_proc_addr(CStr::from_ptr(p_name))
+}
+
+fn mock_proc_addr(name: &CStr) -> vk::PFN_vkVoidFunction {
+    // functions whose signature differs from the usual vkDestroy* one
+    let function = unsafe {
+        match name.to_bytes() {
+            b"vkGetDeviceProcAddr" => std::mem::transmute::<vk::PFN_vkGetDeviceProcAddr, unsafe extern "system" fn()>(
+                mock_get_device_proc_addr,
+            ),
+            b"vkDestroyInstance" => {
+                std::mem::transmute::<vk::PFN_vkDestroyInstance, unsafe extern "system" fn()>(destroy_instance)
+            }
+            b"vkDestroyDevice" => {
+                std::mem::transmute::<vk::PFN_vkDestroyDevice, unsafe extern "system" fn()>(destroy_device)
+            }
+            b"vkFreeCommandBuffers" => {
+                std::mem::transmute::<vk::PFN_vkFreeCommandBuffers, unsafe extern "system" fn()>(free_command_buffers)
+            }
+            b"vkFreeDescriptorSets" => {
+                std::mem::transmute::<vk::PFN_vkFreeDescriptorSets, unsafe extern "system" fn()>(free_descriptor_sets)
+            }
+            b"vkWaitForFences" => {
+                std::mem::transmute::<vk::PFN_vkWaitForFences, unsafe extern "system" fn()>(wait_for_fences)
+            }
+            b"vkWaitSemaphores" | b"vkWaitSemaphoresKHR" => {
+                std::mem::transmute::<vk::PFN_vkWaitSemaphores, unsafe extern "system" fn()>(wait_semaphores)
+            }
+            b"vkDeviceWaitIdle" => {
+                std::mem::transmute::<vk::PFN_vkDeviceWaitIdle, unsafe extern "system" fn()>(device_wait_idle)
+            }
+            b"vkQueueWaitIdle" => {
+                std::mem::transmute::<vk::PFN_vkQueueWaitIdle, unsafe extern "system" fn()>(queue_wait_idle)
+            }
+            b"vkReleasePerformanceConfigurationINTEL" => std::mem::transmute::<
+                vk::PFN_vkReleasePerformanceConfigurationINTEL,
+                unsafe extern "system" fn(),
+            >(release_performance_configuration),
+            _ => {
+                return MOCK_DESTROY_FNS
+                    .iter()
+                    .find(|(fn_name, _)| *fn_name == name)
+                    .and_then(|(_, function)| *function)
+            }
+        }
+    };
+    Some(function)
+}
@@ -0,0 +1,270 @@
+use std::{collections::BTreeMap, fmt::Write, ops::Deref, sync::Mutex};
+
+use ash::vk::{self, Handle};
+
+use crate::field_path;
+
+// a single destruction, `path` is the field being destroyed when the call happened,
+// e.g. `framebuffers[2]`, and is empty for values destroyed outside of a derived impl
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DestructionEntry {
+    pub object_type: vk::ObjectType,
+    pub handle: u64,
+    pub path: String,
+}
+
+struct Recording {
+    original: ash::Device,
+    entries: Vec<DestructionEntry>,
+}
+
+// recordings of every live recorder, indexed by the raw handle of the wrapped device
+static RECORDINGS: Mutex<BTreeMap<u64, Recording>> = Mutex::new(BTreeMap::new());
+
+// destructions made through the device of a dropped recorder, which can't be forwarded anymore
+static STRAY: Mutex<Vec<DestructionEntry>> = Mutex::new(Vec::new());
+
+fn recordings() -> std::sync::MutexGuard<'static, BTreeMap<u64, Recording>> {
+    RECORDINGS.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+fn stray() -> std::sync::MutexGuard<'static, Vec<DestructionEntry>> {
+    STRAY.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+// records the destruction and returns the function of the wrapped device it has to be forwarded to
+fn record<H: Handle + Copy, F>(
+    device: vk::Device,
+    handle: H,
+    original_fn: impl FnOnce(&ash::Device) -> F,
+) -> Option<F> {
+    record_all(device, &[handle], original_fn)
+}
+
+// same as record, for functions freeing several handles at once
+//
+// called from the extern "system" functions, which must not panic: the destructions made through
+// a dropped recorder are kept aside and reported by DestructionRecorder::take_stray_entries
+fn record_all<H: Handle + Copy, F>(
+    device: vk::Device,
+    handles: &[H],
+    original_fn: impl FnOnce(&ash::Device) -> F,
+) -> Option<F> {
+    let path = field_path::current();
+    let entries = handles.iter().map(|handle| DestructionEntry {
+        object_type: H::TYPE,
+        handle: handle.as_raw(),
+        path: path.clone(),
+    });
+    let mut recordings = recordings();
+    let Some(recording) = recordings.get_mut(&device.as_raw()) else {
+        stray().extend(entries);
+        return None;
+    };
+    recording.entries.extend(entries);
+    Some(original_fn(&recording.original))
+}
+
+// wraps a device so that every core destruction function called through it is logged
+// before being forwarded to the wrapped device
+//
+// functions of extension loaders are not recorded, as they aren't loaded through the device
+pub struct DestructionRecorder {
+    device: ash::Device,
+}
+
+impl DestructionRecorder {
+    /// # Safety
+    /// `device` must outlive the recorder, and it must be the only recorder wrapping `device`.
+    pub unsafe fn wrap(device: &ash::Device) -> Self {
+        let mut recordings = recordings();
+        assert!(
+            !recordings.contains_key(&device.handle().as_raw()),
+            "device is already wrapped by a DestructionRecorder"
+        );
+        recordings.insert(
+            device.handle().as_raw(),
+            Recording {
+                original: device.clone(),
+                entries: Vec::new(),
+            },
+        );
+
+        let mut fp_v1_0 = device.fp_v1_0().clone();
+        let mut fp_v1_1 = device.fp_v1_1().clone();
+        let mut fp_v1_3 = device.fp_v1_3().clone();
+        replace_destroy_fns(&mut fp_v1_0, &mut fp_v1_1, &mut fp_v1_3);
+        let device = ash::Device::from_parts_1_3(device.handle(), fp_v1_0, fp_v1_1, device.fp_v1_2().clone(), fp_v1_3);
+        Self { device }
+    }
+
+    pub fn device(&self) -> &ash::Device {
+        &self.device
+    }
+
+    // every destruction recorded so far, in call order
+    pub fn entries(&self) -> Vec<DestructionEntry> {
+        recordings()
+            .get(&self.device.handle().as_raw())
+            .map(|recording| recording.entries.clone())
+            .unwrap_or_default()
+    }
+
+    // same as entries, but also clears the log
+    pub fn take_entries(&self) -> Vec<DestructionEntry> {
+        recordings()
+            .get_mut(&self.device.handle().as_raw())
+            .map(|recording| std::mem::take(&mut recording.entries))
+            .unwrap_or_default()
+    }
+
+    // destructions made through the device of a recorder after it was dropped, which never reached
+    // the wrapped device. empty unless a clone of a recorder's device outlived it
+    pub fn take_stray_entries() -> Vec<DestructionEntry> {
+        std::mem::take(&mut *stray())
+    }
+
+    // the entries as a JSON array with one entry per line, meant for snapshot tests
+    pub fn to_json(&self) -> String {
+        entries_to_json(&self.entries())
+    }
+}
+
+impl Deref for DestructionRecorder {
+    type Target = ash::Device;
+
+    fn deref(&self) -> &ash::Device {
+        &self.device
+    }
+}
+
+impl Drop for DestructionRecorder {
+    fn drop(&mut self) {
+        recordings().remove(&self.device.handle().as_raw());
+    }
+}
+
+pub fn entries_to_json(entries: &[DestructionEntry]) -> String {
+    let mut json = String::from("[");
+    for (i, entry) in entries.iter().enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+        let _ = write!(
+            json,
+            "\n  {{\"object_type\": \"{:?}\", \"handle\": {}, \"path\": \"",
+            entry.object_type, entry.handle
+        );
+        for c in entry.path.chars() {
+            match c {
+                '"' => json.push_str("\\\""),
+                '\\' => json.push_str("\\\\"),
+                c if c.is_control() => {
+                    let _ = write!(json, "\\u{:04x}", c as u32);
+                }
+                c => json.push(c),
+            }
+        }
+        json.push_str("\"}");
+    }
+    if !entries.is_empty() {
+        json.push('\n');
+    }
+    json.push(']');
+    json
+}
+
+macro_rules! recorded_destroy_fns {
+    ($($table:ident.$fn_name:ident($handle:ty)),* $(,)?) => {
+        mod recorded {
+            use super::*;
+
+            $(
+                pub unsafe extern "system" fn $fn_name(
+                    device: vk::Device,
+                    handle: $handle,
+                    p_allocator: *const vk::AllocationCallbacks<'_>,
+                ) {
+                    if let Some(original) = record(device, handle, |original| original.$table().$fn_name) {
+                        original(device, handle, p_allocator);
+                    }
+                }
+            )*
+
+            pub unsafe extern "system" fn destroy_device(
+                device: vk::Device,
+                p_allocator: *const vk::AllocationCallbacks<'_>,
+            ) {
+                if let Some(original) = record(device, device, |original| original.fp_v1_0().destroy_device) {
+                    original(device, p_allocator);
+                }
+            }
+
+            pub unsafe extern "system" fn free_command_buffers(
+                device: vk::Device,
+                command_pool: vk::CommandPool,
+                command_buffer_count: u32,
+                p_command_buffers: *const vk::CommandBuffer,
+            ) {
+                let buffers = std::slice::from_raw_parts(p_command_buffers, command_buffer_count as usize);
+                if let Some(original) = record_all(device, buffers, |original| original.fp_v1_0().free_command_buffers) {
+                    original(device, command_pool, command_buffer_count, p_command_buffers);
+                }
+            }
+
+            pub unsafe extern "system" fn free_descriptor_sets(
+                device: vk::Device,
+                descriptor_pool: vk::DescriptorPool,
+                descriptor_set_count: u32,
+                p_descriptor_sets: *const vk::DescriptorSet,
+            ) -> vk::Result {
+                let sets = std::slice::from_raw_parts(p_descriptor_sets, descriptor_set_count as usize);
+                match record_all(device, sets, |original| original.fp_v1_0().free_descriptor_sets) {
+                    Some(original) => original(device, descriptor_pool, descriptor_set_count, p_descriptor_sets),
+                    None => vk::Result::ERROR_UNKNOWN,
+                }
+            }
+        }
+
+        fn replace_destroy_fns(
+            fp_v1_0: &mut ash::DeviceFnV1_0,
+            fp_v1_1: &mut ash::DeviceFnV1_1,
+            fp_v1_3: &mut ash::DeviceFnV1_3,
+        ) {
+            fp_v1_0.destroy_device = recorded::destroy_device;
+            fp_v1_0.free_command_buffers = recorded::free_command_buffers;
+            fp_v1_0.free_descriptor_sets = recorded::free_descriptor_sets;
+            $(
+                recorded_destroy_fns!(@table fp_v1_0, fp_v1_1, fp_v1_3, $table).$fn_name = recorded::$fn_name;
+            )*
+        }
+    };
+    (@table $fp_v1_0:ident, $fp_v1_1:ident, $fp_v1_3:ident, fp_v1_0) => { $fp_v1_0 };
+    (@table $fp_v1_0:ident, $fp_v1_1:ident, $fp_v1_3:ident, fp_v1_1) => { $fp_v1_1 };
+    (@table $fp_v1_0:ident, $fp_v1_1:ident, $fp_v1_3:ident, fp_v1_3) => { $fp_v1_3 };
+}
+
+recorded_destroy_fns! {
+    fp_v1_3.destroy_private_data_slot(vk::PrivateDataSlot),
+    fp_v1_1.destroy_sampler_ycbcr_conversion(vk::SamplerYcbcrConversion),
+    fp_v1_1.destroy_descriptor_update_template(vk::DescriptorUpdateTemplate),
+    fp_v1_0.destroy_sampler(vk::Sampler),
+    fp_v1_0.destroy_fence(vk::Fence),
+    fp_v1_0.destroy_event(vk::Event),
+    fp_v1_0.destroy_image(vk::Image),
+    fp_v1_0.destroy_command_pool(vk::CommandPool),
+    fp_v1_0.destroy_image_view(vk::ImageView),
+    fp_v1_0.destroy_render_pass(vk::RenderPass),
+    fp_v1_0.destroy_framebuffer(vk::Framebuffer),
+    fp_v1_0.destroy_pipeline_layout(vk::PipelineLayout),
+    fp_v1_0.destroy_pipeline_cache(vk::PipelineCache),
+    fp_v1_0.destroy_buffer(vk::Buffer),
+    fp_v1_0.destroy_shader_module(vk::ShaderModule),
+    fp_v1_0.destroy_pipeline(vk::Pipeline),
+    fp_v1_0.destroy_semaphore(vk::Semaphore),
+    fp_v1_0.destroy_descriptor_pool(vk::DescriptorPool),
+    fp_v1_0.destroy_query_pool(vk::QueryPool),
+    fp_v1_0.destroy_descriptor_set_layout(vk::DescriptorSetLayout),
+    fp_v1_0.destroy_buffer_view(vk::BufferView),
+    fp_v1_0.free_memory(vk::DeviceMemory),
+}
@@ -0,0 +1,168 @@
+use ash::vk::{self, Handle};
+
+use crate::DeviceDestroyable;
+
+// waits for the GPU work using a value to complete before destroying it
+//
+// on timeout or device loss the value is not destroyed and the error is returned, so that
+// resources that may still be in use are never freed
+
+/// # Safety
+/// `destroyable` must have been created from `device`, and `fence` must be signaled once the
+/// last work using it completes. See [`DeviceDestroyable::destroy_self_alloc`].
+pub unsafe fn destroy_after_fence<T: DeviceDestroyable + ?Sized>(
+    device: &ash::Device,
+    fence: vk::Fence,
+    timeout: u64,
+    destroyable: &T,
+) -> Result<(), vk::Result> {
+    destroy_after_fence_alloc(device, fence, timeout, destroyable, None)
+}
+
+/// # Safety
+/// See [`destroy_after_fence`].
+pub unsafe fn destroy_after_fence_alloc<T: DeviceDestroyable + ?Sized>(
+    device: &ash::Device,
+    fence: vk::Fence,
+    timeout: u64,
+    destroyable: &T,
+    allocation_callbacks: Option<&vk::AllocationCallbacks>,
+) -> Result<(), vk::Result> {
+    device.wait_for_fences(&[fence], true, timeout)?;
+    destroyable.destroy_self_alloc(device, allocation_callbacks);
+    Ok(())
+}
+
+/// # Safety
+/// `destroyable` must have been created from `device`, and the timeline `semaphore` must reach
+/// `value` once the last work using it completes. See [`DeviceDestroyable::destroy_self_alloc`].
+pub unsafe fn destroy_after_timeline<T: DeviceDestroyable + ?Sized>(
+    device: &ash::Device,
+    semaphore: vk::Semaphore,
+    value: u64,
+    timeout: u64,
+    destroyable: &T,
+) -> Result<(), vk::Result> {
+    destroy_after_timeline_alloc(device, semaphore, value, timeout, destroyable, None)
+}
+
+/// # Safety
+/// See [`destroy_after_timeline`].
+pub unsafe fn destroy_after_timeline_alloc<T: DeviceDestroyable + ?Sized>(
+    device: &ash::Device,
+    semaphore: vk::Semaphore,
+    value: u64,
+    timeout: u64,
+    destroyable: &T,
+    allocation_callbacks: Option<&vk::AllocationCallbacks>,
+) -> Result<(), vk::Result> {
+    let semaphores = [semaphore];
+    let values = [value];
+    let wait_info = vk::SemaphoreWaitInfo::default()
+        .semaphores(&semaphores)
+        .values(&values);
+    device.wait_semaphores(&wait_info, timeout)?;
+    destroyable.destroy_self_alloc(device, allocation_callbacks);
+    Ok(())
+}
+
+// the wait of a teardown that failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DestroyStep {
+    DeviceWaitIdle,
+    QueueWaitIdle(vk::Queue),
+}
+
+// a teardown that failed before anything was destroyed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DestroyError {
+    pub step: DestroyStep,
+    pub result: vk::Result,
+}
+
+impl std::fmt::Display for DestroyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.step {
+            DestroyStep::DeviceWaitIdle => write!(f, "waiting for the device to be idle failed with {:?}", self.result),
+            DestroyStep::QueueWaitIdle(queue) => write!(
+                f,
+                "waiting for queue 0x{:x} to be idle failed with {:?}",
+                queue.as_raw(),
+                self.result
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DestroyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.result)
+    }
+}
+
+impl From<DestroyError> for vk::Result {
+    fn from(error: DestroyError) -> Self {
+        error.result
+    }
+}
+
+// waits for the whole device to be idle before destroying `destroyable`, e.g. a renderer
+// being torn down
+//
+// when the wait fails, e.g. with ERROR_DEVICE_LOST, nothing is destroyed and the caller decides
+// whether destroying is still possible
+
+/// # Safety
+/// `destroyable` must have been created from `device`. See [`DeviceDestroyable::destroy_self_alloc`].
+pub unsafe fn try_destroy_all<T: DeviceDestroyable + ?Sized>(
+    device: &ash::Device,
+    destroyable: &T,
+) -> Result<(), DestroyError> {
+    try_destroy_all_alloc(device, destroyable, None)
+}
+
+/// # Safety
+/// See [`try_destroy_all`].
+pub unsafe fn try_destroy_all_alloc<T: DeviceDestroyable + ?Sized>(
+    device: &ash::Device,
+    destroyable: &T,
+    allocation_callbacks: Option<&vk::AllocationCallbacks>,
+) -> Result<(), DestroyError> {
+    device.device_wait_idle().map_err(|result| DestroyError {
+        step: DestroyStep::DeviceWaitIdle,
+        result,
+    })?;
+    destroyable.destroy_self_alloc(device, allocation_callbacks);
+    Ok(())
+}
+
+// same as try_destroy_all, but only waits for the given queues to be idle
+
+/// # Safety
+/// `destroyable` must have been created from `device` and must only be in use by work submitted
+/// to `queues`. See [`DeviceDestroyable::destroy_self_alloc`].
+pub unsafe fn try_destroy_all_after_queues<T: DeviceDestroyable + ?Sized>(
+    device: &ash::Device,
+    queues: &[vk::Queue],
+    destroyable: &T,
+) -> Result<(), DestroyError> {
+    try_destroy_all_after_queues_alloc(device, queues, destroyable, None)
+}
+
+/// # Safety
+/// See [`try_destroy_all_after_queues`].
+pub unsafe fn try_destroy_all_after_queues_alloc<T: DeviceDestroyable + ?Sized>(
+    device: &ash::Device,
+    queues: &[vk::Queue],
+    destroyable: &T,
+    allocation_callbacks: Option<&vk::AllocationCallbacks>,
+) -> Result<(), DestroyError> {
+    for &queue in queues {
+        device.queue_wait_idle(queue).map_err(|result| DestroyError {
+            step: DestroyStep::QueueWaitIdle(queue),
+            result,
+        })?;
+    }
+    destroyable.destroy_self_alloc(device, allocation_callbacks);
+    Ok(())
+}
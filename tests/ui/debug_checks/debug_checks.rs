@@ -0,0 +1,103 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use ash::vk::{self, Handle};
+use ash_destructor::{
+    debug_checks::{self, OnViolation},
+    testing::{DestroyCall, MockDevice},
+    track_created, with_null_handle_policy, DeviceDestroyable, NullHandlePolicy, PooledCommandBuffers, SelfDestroyable,
+};
+
+fn main() {
+    // keep the expected panics out of the output
+    std::panic::set_hook(Box::new(|_| {}));
+
+    let mock = MockDevice::new();
+    let device = mock.device().handle();
+    let image = track_created(device, vk::Image::from_raw(1));
+    unsafe {
+        image.destroy_self(&mock);
+    }
+
+    let panic = catch_unwind(AssertUnwindSafe(|| unsafe { image.destroy_self(&mock) })).unwrap_err();
+    assert_eq!(
+        panic.downcast_ref::<String>().unwrap(),
+        &format!("ash_destructor: IMAGE 0x1 was destroyed twice through 0x{:x}", device.as_raw())
+    );
+    // null handles are skipped before reaching the checks unless they are destroyed too
+    unsafe { vk::Buffer::null().destroy_self(&mock) };
+    let panic = catch_unwind(AssertUnwindSafe(|| {
+        with_null_handle_policy(NullHandlePolicy::Destroy, || unsafe { vk::Buffer::null().destroy_self(&mock) })
+    }))
+    .unwrap_err();
+    assert_eq!(
+        panic.downcast_ref::<String>().unwrap(),
+        "ash_destructor: a null BUFFER handle was destroyed"
+    );
+    // the policy is restored after a panic and only applies to the calling thread
+    unsafe { vk::Buffer::null().destroy_self(&mock) };
+    with_null_handle_policy(NullHandlePolicy::Destroy, || {
+        std::thread::scope(|scope| {
+            scope.spawn(|| unsafe { vk::Buffer::null().destroy_self(&mock) });
+        })
+    });
+    // the faulty destructions never reach the driver
+    assert_eq!(mock.take_calls(), [DestroyCall::new(image, None)]);
+
+    // handles are tracked per device
+    let other = MockDevice::new();
+    unsafe {
+        track_created(other.device().handle(), image).destroy_self(&other);
+    }
+    assert_eq!(other.take_calls(), [DestroyCall::new(image, None)]);
+
+    // a handle value reused by the driver can be destroyed again once reported
+    debug_checks::created(device, image);
+    unsafe {
+        image.destroy_self(&mock);
+    }
+    assert_eq!(mock.take_calls(), [DestroyCall::new(image, None)]);
+
+    // handles whose creation wasn't reported aren't checked
+    let buffer = vk::Buffer::from_raw(0x1000);
+    unsafe {
+        buffer.destroy_self(&mock);
+        buffer.destroy_self(&mock);
+    }
+    assert_eq!(mock.take_calls(), [DestroyCall::new(buffer, None); 2]);
+
+    debug_checks::set_on_violation(OnViolation::Print);
+    let pool = vk::CommandPool::from_raw(2);
+    let freed = PooledCommandBuffers {
+        pool,
+        buffers: vec![track_created(device, vk::CommandBuffer::from_raw(3))],
+    };
+    let buffers = PooledCommandBuffers {
+        pool,
+        buffers: vec![vk::CommandBuffer::from_raw(3), track_created(device, vk::CommandBuffer::from_raw(4))],
+    };
+    track_created(device, device);
+    unsafe {
+        freed.destroy_self(&mock);
+        mock.take_calls();
+
+        // with printing only, the faulty destructions are skipped
+        image.destroy_self(&mock);
+        buffers.destroy_self(&mock);
+        SelfDestroyable::destroy_self(mock.device());
+        SelfDestroyable::destroy_self(mock.device());
+    }
+    assert_eq!(
+        mock.take_calls(),
+        [
+            DestroyCall::new(vk::CommandBuffer::from_raw(4), None),
+            DestroyCall::new(device, None),
+        ]
+    );
+
+    // a device recreated with the same raw value, e.g. after a device loss, starts over
+    track_created(device, device);
+    unsafe {
+        SelfDestroyable::destroy_self(mock.device());
+    }
+    assert_eq!(mock.take_calls(), [DestroyCall::new(device, None)]);
+}
@@ -0,0 +1,39 @@
+use ash_destructor::{DeviceDestroyable, InstanceDestroyable, SelfDestroyable};
+
+#[derive(DeviceDestroyable)]
+union Handle {
+    image: ash::vk::Image,
+    buffer: ash::vk::Buffer,
+}
+
+#[derive(SelfDestroyable)]
+union Context {
+    device: std::mem::ManuallyDrop<ash::Device>,
+}
+
+#[derive(DeviceDestroyable)]
+#[destroy(bound = "T: DeviceDestroyable", bound = "")]
+struct RepeatedBound<T> {
+    inner: T,
+}
+
+#[derive(InstanceDestroyable)]
+#[destroy(bound = "T InstanceDestroyable")]
+struct InvalidBound<T> {
+    inner: T,
+}
+
+#[derive(DeviceDestroyable)]
+#[destroy(before = inner)]
+struct UnsupportedAttribute<T> {
+    inner: T,
+}
+
+// the bound given doesn't cover the field
+#[derive(DeviceDestroyable)]
+#[destroy(bound = "")]
+struct MissingBound<T> {
+    inner: T,
+}
+
+fn main() {}
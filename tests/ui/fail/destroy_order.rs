@@ -0,0 +1,39 @@
+use ash::vk;
+use ash_destructor::DeviceDestroyable;
+
+#[derive(DeviceDestroyable)]
+struct Unknown {
+    #[destroy(before = missing)]
+    a: vk::Image,
+    #[destroy(after = 2)]
+    b: vk::Image,
+}
+
+#[derive(DeviceDestroyable)]
+struct Ignored {
+    #[destroy(before = b)]
+    a: vk::Image,
+    #[destroy_ignore]
+    b: vk::Image,
+}
+
+#[derive(DeviceDestroyable)]
+struct Cycle {
+    #[destroy(before = b)]
+    a: vk::Image,
+    #[destroy(before = c)]
+    b: vk::Image,
+    #[destroy(before = a)]
+    c: vk::Image,
+    d: vk::Image,
+}
+
+#[derive(DeviceDestroyable)]
+struct Unsupported {
+    #[destroy(first)]
+    a: vk::Image,
+    #[destroy(before = a)]
+    b: vk::Image,
+}
+
+fn main() {}
@@ -0,0 +1,36 @@
+use ash::vk;
+use ash_destructor::{DeviceDestroyable, InstanceDestroyable};
+
+fn destroy_buffers(_: &Vec<vk::CommandBuffer>, _: &ash::Device, _: Option<&vk::AllocationCallbacks>) {}
+
+#[derive(DeviceDestroyable)]
+struct Attributes {
+    #[destroy(pool = missing)]
+    a: Vec<vk::CommandBuffer>,
+    #[destroy(pool = pool, pool = pool)]
+    b: Vec<vk::CommandBuffer>,
+    #[destroy_ignore]
+    #[destroy(pool = pool)]
+    c: Vec<vk::CommandBuffer>,
+    #[destroy_with = "destroy_buffers"]
+    #[destroy(pool = pool)]
+    d: Vec<vk::CommandBuffer>,
+    pool: vk::CommandPool,
+}
+
+#[derive(DeviceDestroyable)]
+struct WrongPool {
+    #[destroy(pool = pool)]
+    buffers: Vec<vk::CommandBuffer>,
+    pool: vk::DescriptorPool,
+}
+
+#[derive(InstanceDestroyable)]
+struct Instance {
+    #[destroy(pool = pool)]
+    buffers: Vec<vk::CommandBuffer>,
+    #[destroy_ignore]
+    pool: vk::CommandPool,
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+use ash::vk;
+use ash_destructor::DeviceDestroyable;
+
+fn destroy_string(_: &String, _: &ash::Device, _: Option<&vk::AllocationCallbacks>) {}
+
+fn wrong_signature(_: &String) {}
+
+#[derive(DeviceDestroyable)]
+struct Attributes {
+    #[destroy_with(destroy_string)]
+    a: String,
+    #[destroy_with = 1]
+    b: String,
+    #[destroy_with = "not a path"]
+    c: String,
+    #[destroy_ignore]
+    #[destroy_with = "destroy_string"]
+    d: String,
+    #[destroy_with = "destroy_string"]
+    #[destroy_with = "destroy_string"]
+    e: String,
+}
+
+#[derive(DeviceDestroyable)]
+struct Signature {
+    #[destroy_with = "wrong_signature"]
+    a: String,
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+use ash_destructor::DeviceDestroyable;
+
+#[path = "../../utils/mod.rs"]
+mod utils;
+
+use utils::ImplDeviceDestroyable;
+
+#[derive(DeviceDestroyable)]
+enum Resource {
+    Named {
+        #[destroy_ignore]
+        #[destroy_ignore]
+        a: ImplDeviceDestroyable,
+    },
+    Unnamed(
+        #[destroy_ignore_remaining] ImplDeviceDestroyable,
+        #[destroy_ignore_remaining] ImplDeviceDestroyable,
+    ),
+    // attributes of one variant don't affect the others
+    Other(ImplDeviceDestroyable, #[destroy_ignore_remaining] String),
+    Unit,
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use ash_destructor::DeviceDestroyable;
+
+#[path = "../../utils/mod.rs"]
+mod utils;
+
+use utils::ImplDeviceDestroyable;
+
+#[derive(DeviceDestroyable)]
+enum Resource {
+    Named {
+        a: ImplDeviceDestroyable,
+        // doesn't implement trait
+        b: String,
+    },
+    Unnamed(ImplDeviceDestroyable, String),
+    Unit,
+}
+
+fn main() {}
@@ -0,0 +1,38 @@
+use ash::vk;
+use ash_destructor::{DeviceDestroyable, SelfDestroyable};
+
+#[derive(DeviceDestroyable)]
+struct Attributes {
+    #[destroy(alloc = self.missing)]
+    a: vk::Image,
+    #[destroy(alloc = none, alloc = none)]
+    b: vk::Image,
+    #[destroy(alloc = default)]
+    c: vk::Image,
+    #[destroy_ignore]
+    #[destroy(alloc = none)]
+    d: vk::Image,
+    #[destroy(pool = pool, alloc = self.callbacks)]
+    e: Vec<vk::CommandBuffer>,
+    pool: vk::CommandPool,
+    #[destroy_ignore]
+    callbacks: vk::AllocationCallbacks<'static>,
+}
+
+#[derive(DeviceDestroyable)]
+struct NotCallbacks {
+    #[destroy(alloc = self.callbacks)]
+    image: vk::Image,
+    #[destroy_ignore]
+    callbacks: u32,
+}
+
+#[derive(SelfDestroyable)]
+struct Device {
+    image: vk::Image,
+    #[destroy_device]
+    #[destroy(alloc = none)]
+    device: ash::Device,
+}
+
+fn main() {}
@@ -0,0 +1,36 @@
+use ash::vk;
+use ash_destructor::{DeviceDestroyable, SelfDestroyable};
+
+#[derive(SelfDestroyable)]
+struct NoDevice {
+    pools: Vec<vk::CommandPool>,
+}
+
+#[derive(SelfDestroyable)]
+struct TwoDevices {
+    #[destroy_device]
+    first: ash::Device,
+    #[destroy_device]
+    second: ash::Device,
+}
+
+#[derive(SelfDestroyable)]
+struct IgnoredDevice {
+    pools: Vec<vk::CommandPool>,
+    #[destroy_ignore]
+    #[destroy_device]
+    device: ash::Device,
+}
+
+#[derive(SelfDestroyable)]
+enum Context {
+    Device(#[destroy_device] ash::Device),
+}
+
+#[derive(DeviceDestroyable)]
+struct DeviceDerive {
+    #[destroy_device]
+    device: ash::Device,
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use ash::vk;
+use ash_destructor::{DeviceDestroyable, SetNull};
+
+fn free(_: &String, _: &ash::Device, _: Option<&vk::AllocationCallbacks>) {}
+
+#[derive(DeviceDestroyable, SetNull)]
+struct Missing {
+    #[destroy_with = "free"]
+    allocation: String,
+    buffer: vk::Buffer,
+}
+
+#[derive(DeviceDestroyable, SetNull)]
+struct Unnamed(vk::Buffer, #[destroy_with = "free"] String);
+
+#[derive(DeviceDestroyable, SetNull)]
+struct Invalid {
+    #[destroy_with = "free"]
+    #[destroy(null_with = default)]
+    allocation: String,
+}
+
+fn main() {}
@@ -0,0 +1,29 @@
+use ash::vk;
+use ash_destructor::{DeviceDestroyable, SelfDestroyable};
+
+#[derive(DeviceDestroyable)]
+struct Frame {
+    fence: vk::Fence,
+    semaphore: vk::Semaphore,
+}
+
+#[derive(SelfDestroyable)]
+struct Context {
+    #[destroy_device]
+    device: ash::Device,
+    frame: Frame,
+}
+
+fn use_frame(_: &Frame) {}
+
+unsafe fn destroy_frame(frame: Frame, device: &ash::Device) {
+    frame.destroy(device);
+    use_frame(&frame);
+}
+
+unsafe fn destroy_context(context: Context) {
+    SelfDestroyable::destroy(context);
+    use_frame(&context.frame);
+}
+
+fn main() {}
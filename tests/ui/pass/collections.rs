@@ -0,0 +1,56 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList, VecDeque};
+
+use ash::vk::{self, Handle};
+use ash_destructor::{
+    testing::{DestructionRecorder, MockDevice},
+    DeviceDestroyable,
+};
+
+#[derive(DeviceDestroyable)]
+struct Caches {
+    pipelines: HashMap<&'static str, vk::Pipeline>,
+    layouts: BTreeMap<u32, vk::PipelineLayout>,
+    frames: VecDeque<vk::Fence>,
+    samplers: HashSet<vk::Sampler>,
+    modules: BTreeSet<vk::ShaderModule>,
+    events: LinkedList<vk::Event>,
+}
+
+fn main() {
+    let mock = MockDevice::new();
+    let recorder = unsafe { DestructionRecorder::wrap(&mock) };
+
+    let caches = Caches {
+        pipelines: HashMap::from([("opaque", vk::Pipeline::from_raw(1)), ("shadow", vk::Pipeline::from_raw(2))]),
+        layouts: BTreeMap::from([(7, vk::PipelineLayout::from_raw(3)), (2, vk::PipelineLayout::from_raw(4))]),
+        frames: VecDeque::from([vk::Fence::from_raw(5), vk::Fence::from_raw(6)]),
+        samplers: HashSet::from([vk::Sampler::from_raw(7), vk::Sampler::from_raw(8)]),
+        modules: BTreeSet::from([vk::ShaderModule::from_raw(10), vk::ShaderModule::from_raw(9)]),
+        events: LinkedList::from([vk::Event::from_raw(11), vk::Event::from_raw(12)]),
+    };
+    unsafe {
+        caches.destroy_self(&recorder);
+    }
+
+    let entries = recorder.take_entries();
+    let ordered = |path: &str| {
+        entries
+            .iter()
+            .filter(|entry| entry.path.starts_with(path))
+            .map(|entry| (entry.handle, entry.path.as_str()))
+            .collect::<Vec<_>>()
+    };
+    // sequences are destroyed back-to-front with the index of every element
+    assert_eq!(ordered("events"), [(12, "events[1]"), (11, "events[0]")]);
+    assert_eq!(ordered("frames"), [(6, "frames[1]"), (5, "frames[0]")]);
+    // sorted collections from the largest key to the smallest
+    assert_eq!(ordered("modules"), [(10, "modules"), (9, "modules")]);
+    assert_eq!(ordered("layouts"), [(3, "layouts"), (4, "layouts")]);
+    // hashed collections in an unspecified order
+    let mut samplers = ordered("samplers");
+    samplers.sort();
+    assert_eq!(samplers, [(7, "samplers"), (8, "samplers")]);
+    let mut pipelines = ordered("pipelines");
+    pipelines.sort();
+    assert_eq!(pipelines, [(1, "pipelines"), (2, "pipelines")]);
+}
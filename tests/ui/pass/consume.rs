@@ -0,0 +1,46 @@
+use ash::vk::{self, Handle};
+use ash_destructor::{
+    testing::{DestructionRecorder, MockDevice},
+    DeviceDestroyable,
+};
+
+#[derive(DeviceDestroyable)]
+struct Frame {
+    fence: vk::Fence,
+    semaphore: vk::Semaphore,
+}
+
+fn main() {
+    let mock = MockDevice::new();
+    let recorder = unsafe { DestructionRecorder::wrap(&mock) };
+
+    let frame = Frame {
+        fence: vk::Fence::from_raw(1),
+        semaphore: vk::Semaphore::from_raw(2),
+    };
+    let frames = vec![Frame {
+        fence: vk::Fence::from_raw(3),
+        semaphore: vk::Semaphore::from_raw(4),
+    }];
+    unsafe {
+        frame.destroy(&recorder);
+        frames.destroy_alloc(&recorder, None);
+        vk::Buffer::from_raw(5).destroy(&recorder);
+    }
+
+    let destroyed = recorder
+        .entries()
+        .iter()
+        .map(|entry| (entry.object_type, entry.handle))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        destroyed,
+        [
+            (vk::ObjectType::SEMAPHORE, 2),
+            (vk::ObjectType::FENCE, 1),
+            (vk::ObjectType::SEMAPHORE, 4),
+            (vk::ObjectType::FENCE, 3),
+            (vk::ObjectType::BUFFER, 5),
+        ]
+    );
+}
@@ -0,0 +1,61 @@
+use ash::vk::{self, Handle};
+use ash_destructor::{
+    testing::{DestroyCall, MockDevice},
+    DeferredDestroyer, DeviceDestroyable,
+};
+
+#[derive(DeviceDestroyable)]
+struct Staging {
+    memory: vk::DeviceMemory,
+    buffer: vk::Buffer,
+}
+
+fn main() {
+    let mock = MockDevice::new();
+    let mut destroyer = DeferredDestroyer::new();
+    destroyer.push(
+        1,
+        Staging {
+            memory: vk::DeviceMemory::from_raw(1),
+            buffer: vk::Buffer::from_raw(2),
+        },
+    );
+    destroyer.push(2, vk::Image::from_raw(3));
+    destroyer.push(1, vk::ImageView::from_raw(4));
+    destroyer.push_boxed(3, Box::new(vec![vk::Sampler::from_raw(5), vk::Sampler::from_raw(6)]));
+    assert_eq!(destroyer.len(), 4);
+
+    // the destroyer can be handed to the thread collecting it
+    let mut destroyer = std::thread::spawn(move || destroyer).join().unwrap();
+
+    unsafe {
+        assert_eq!(destroyer.collect(&mock, 0), 0);
+        assert!(mock.calls().is_empty());
+
+        // values whose work completed are destroyed in reverse push order
+        assert_eq!(destroyer.collect(&mock, 1), 2);
+    }
+    assert_eq!(
+        mock.take_calls(),
+        [
+            DestroyCall::new(vk::ImageView::from_raw(4), None),
+            DestroyCall::new(vk::Buffer::from_raw(2), None),
+            DestroyCall::new(vk::DeviceMemory::from_raw(1), None),
+        ]
+    );
+    assert_eq!(destroyer.len(), 2);
+
+    let allocation_callbacks = vk::AllocationCallbacks::default();
+    unsafe {
+        destroyer.flush_all_alloc(&mock, Some(&allocation_callbacks));
+    }
+    assert_eq!(
+        mock.take_calls(),
+        [
+            DestroyCall::new(vk::Sampler::from_raw(6), Some(&allocation_callbacks)),
+            DestroyCall::new(vk::Sampler::from_raw(5), Some(&allocation_callbacks)),
+            DestroyCall::new(vk::Image::from_raw(3), Some(&allocation_callbacks)),
+        ]
+    );
+    assert!(destroyer.is_empty());
+}
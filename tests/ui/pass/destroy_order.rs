@@ -0,0 +1,65 @@
+use ash::vk::{self, Handle};
+use ash_destructor::{
+    testing::{DestroyCall, MockDevice},
+    DeviceDestroyable,
+};
+
+#[derive(DeviceDestroyable)]
+struct Swapchain {
+    #[destroy(before = views, before = render_pass)]
+    framebuffer: vk::Framebuffer,
+    render_pass: vk::RenderPass,
+    views: Vec<vk::ImageView>,
+    #[destroy(after = render_pass)]
+    layout: vk::PipelineLayout,
+    #[destroy_ignore]
+    _extent: vk::Extent2D,
+}
+
+#[derive(DeviceDestroyable)]
+struct Memory(#[destroy(after = 1)] vk::DeviceMemory, vk::Image);
+
+#[derive(DeviceDestroyable)]
+enum Resource {
+    Buffer {
+        #[destroy(after = buffer)]
+        memory: vk::DeviceMemory,
+        buffer: vk::Buffer,
+    },
+}
+
+fn main() {
+    let mock = MockDevice::new();
+    let swapchain = Swapchain {
+        framebuffer: vk::Framebuffer::from_raw(1),
+        render_pass: vk::RenderPass::from_raw(2),
+        views: vec![vk::ImageView::from_raw(3), vk::ImageView::from_raw(4)],
+        layout: vk::PipelineLayout::from_raw(5),
+        _extent: vk::Extent2D::default(),
+    };
+    let memory = Memory(vk::DeviceMemory::from_raw(6), vk::Image::from_raw(7));
+    let resource = Resource::Buffer {
+        memory: vk::DeviceMemory::from_raw(8),
+        buffer: vk::Buffer::from_raw(9),
+    };
+    unsafe {
+        swapchain.destroy_self(&mock);
+        memory.destroy_self(&mock);
+        resource.destroy_self(&mock);
+    }
+
+    assert_eq!(
+        mock.take_calls(),
+        [
+            DestroyCall::new(vk::Framebuffer::from_raw(1), None),
+            DestroyCall::new(vk::ImageView::from_raw(4), None),
+            DestroyCall::new(vk::ImageView::from_raw(3), None),
+            DestroyCall::new(vk::RenderPass::from_raw(2), None),
+            DestroyCall::new(vk::PipelineLayout::from_raw(5), None),
+            DestroyCall::new(vk::Image::from_raw(7), None),
+            DestroyCall::new(vk::DeviceMemory::from_raw(6), None),
+            DestroyCall::new(vk::Buffer::from_raw(9), None),
+            DestroyCall::new(vk::DeviceMemory::from_raw(8), None),
+        ]
+    );
+}
@@ -0,0 +1,87 @@
+use std::{
+    cell::{LazyCell, OnceCell},
+    sync::{LazyLock, OnceLock},
+};
+
+use ash::vk::{self, Handle};
+use ash_destructor::{
+    testing::{DestructionRecorder, MockDevice},
+    DestroyTake, DeviceDestroyable,
+};
+
+static UNUSED_SAMPLER: LazyLock<vk::Sampler> = LazyLock::new(|| unreachable!("destroying must not create the sampler"));
+
+#[derive(DeviceDestroyable)]
+struct Frame {
+    fence: vk::Fence,
+    semaphore: vk::Semaphore,
+}
+
+fn main() {
+    let mock = MockDevice::new();
+    let recorder = unsafe { DestructionRecorder::wrap(&mock) };
+    let destroyed = || {
+        recorder
+            .take_entries()
+            .iter()
+            .map(|entry| (entry.object_type, entry.handle, entry.path.clone()))
+            .collect::<Vec<_>>()
+    };
+
+    // uninitialized lazy values are not created
+    let unused: LazyCell<vk::Sampler> = LazyCell::new(|| unreachable!("destroying must not create the sampler"));
+    let used: LazyCell<vk::Sampler> = LazyCell::new(|| vk::Sampler::from_raw(1));
+    LazyCell::force(&used);
+    unsafe {
+        unused.destroy_self(&recorder);
+        UNUSED_SAMPLER.destroy_self(&recorder);
+        used.destroy_self(&recorder);
+        OnceCell::<vk::Buffer>::new().destroy_self(&recorder);
+    }
+    assert_eq!(destroyed(), [(vk::ObjectType::SAMPLER, 1, String::new())]);
+
+    let mut swapchain_image = Some(vk::ImageView::from_raw(2));
+    let mut pipeline = OnceCell::from(vk::Pipeline::from_raw(3));
+    let mut layout = OnceLock::from(vk::PipelineLayout::from_raw(4));
+    let mut frames = vec![
+        Frame {
+            fence: vk::Fence::from_raw(5),
+            semaphore: vk::Semaphore::from_raw(6),
+        },
+        Frame {
+            fence: vk::Fence::from_raw(7),
+            semaphore: vk::Semaphore::from_raw(8),
+        },
+    ];
+    unsafe {
+        swapchain_image.destroy_take(&recorder);
+        pipeline.destroy_take(&recorder);
+        layout.destroy_take(&recorder);
+        frames.destroy_take(&recorder);
+    }
+    assert_eq!(swapchain_image, None);
+    assert_eq!(pipeline.get(), None);
+    assert_eq!(layout.get(), None);
+    assert!(frames.is_empty());
+    assert_eq!(
+        destroyed(),
+        [
+            (vk::ObjectType::IMAGE_VIEW, 2, String::new()),
+            (vk::ObjectType::PIPELINE, 3, String::new()),
+            (vk::ObjectType::PIPELINE_LAYOUT, 4, String::new()),
+            (vk::ObjectType::SEMAPHORE, 8, "[1].semaphore".to_owned()),
+            (vk::ObjectType::FENCE, 7, "[1].fence".to_owned()),
+            (vk::ObjectType::SEMAPHORE, 6, "[0].semaphore".to_owned()),
+            (vk::ObjectType::FENCE, 5, "[0].fence".to_owned()),
+        ]
+    );
+
+    // destroying again does nothing, the containers are empty
+    unsafe {
+        swapchain_image.destroy_take(&recorder);
+        pipeline.destroy_take(&recorder);
+        layout.destroy_take(&recorder);
+        frames.destroy_take(&recorder);
+    }
+    assert_eq!(destroyed(), []);
+}
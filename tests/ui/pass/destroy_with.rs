@@ -0,0 +1,75 @@
+use ash::vk::{self, Handle};
+use ash_destructor::{
+    testing::{DestroyCall, MockDevice},
+    DeviceDestroyable,
+};
+
+// stands in for a third-party type that cannot implement DeviceDestroyable
+mod allocator {
+    use ash::vk;
+
+    pub struct Allocation {
+        pub memory: vk::DeviceMemory,
+    }
+
+    pub unsafe fn free(
+        allocation: &Allocation,
+        device: &ash::Device,
+        allocation_callbacks: Option<&vk::AllocationCallbacks>,
+    ) {
+        device.free_memory(allocation.memory, allocation_callbacks);
+    }
+}
+
+#[derive(DeviceDestroyable)]
+struct Buffer {
+    #[destroy_with = "allocator::free"]
+    allocation: allocator::Allocation,
+    buffer: vk::Buffer,
+}
+
+#[derive(DeviceDestroyable)]
+struct Image(
+    vk::Image,
+    #[destroy_with = "allocator::free"] allocator::Allocation,
+    vk::ImageView,
+);
+
+fn main() {
+    let mock = MockDevice::new();
+    let buffer = Buffer {
+        allocation: allocator::Allocation {
+            memory: vk::DeviceMemory::from_raw(1),
+        },
+        buffer: vk::Buffer::from_raw(2),
+    };
+    let image = Image(
+        vk::Image::from_raw(3),
+        allocator::Allocation {
+            memory: vk::DeviceMemory::from_raw(4),
+        },
+        vk::ImageView::from_raw(5),
+    );
+    let allocation_callbacks = vk::AllocationCallbacks::default();
+    unsafe {
+        buffer.destroy_self(&mock);
+        image.destroy_self_alloc(&mock, Some(&allocation_callbacks));
+    }
+
+    let calls = mock.take_calls();
+    assert_eq!(
+        calls[..2],
+        [
+            DestroyCall::new(vk::Buffer::from_raw(2), None),
+            DestroyCall::new(vk::DeviceMemory::from_raw(1), None),
+        ]
+    );
+    assert_eq!(
+        calls[2..],
+        [
+            DestroyCall::new(vk::ImageView::from_raw(5), Some(&allocation_callbacks)),
+            DestroyCall::new(vk::DeviceMemory::from_raw(4), Some(&allocation_callbacks)),
+            DestroyCall::new(vk::Image::from_raw(3), Some(&allocation_callbacks)),
+        ]
+    );
+}
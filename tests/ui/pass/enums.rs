@@ -0,0 +1,78 @@
+use ash_destructor::DeviceDestroyable;
+
+#[path = "../../utils/mod.rs"]
+mod utils;
+
+use utils::ImplDeviceDestroyable;
+
+#[derive(DeviceDestroyable)]
+enum Resource {
+    Named {
+        a: ImplDeviceDestroyable,
+        #[destroy_ignore]
+        b: ImplDeviceDestroyable,
+        c: ImplDeviceDestroyable,
+    },
+    Unnamed(
+        ImplDeviceDestroyable,
+        #[destroy_ignore_remaining] ImplDeviceDestroyable,
+        usize,
+    ),
+    Unit,
+}
+
+#[derive(DeviceDestroyable)]
+enum Generic<'a, T: DeviceDestroyable> {
+    Borrowed(&'a T),
+    Owned { device: T, allocation_callbacks: Option<T> },
+}
+
+#[derive(DeviceDestroyable)]
+enum Empty {}
+
+fn main() {
+    let device = utils::create_dummy_device();
+
+    let named = Resource::Named {
+        a: ImplDeviceDestroyable::new(),
+        b: ImplDeviceDestroyable::new(),
+        c: ImplDeviceDestroyable::new(),
+    };
+    let unnamed = Resource::Unnamed(ImplDeviceDestroyable::new(), ImplDeviceDestroyable::new(), 0);
+    let unit = Resource::Unit;
+    let owned: Generic<'_, ImplDeviceDestroyable> = Generic::Owned {
+        device: ImplDeviceDestroyable::new(),
+        allocation_callbacks: Some(ImplDeviceDestroyable::new()),
+    };
+    unsafe {
+        named.destroy_self(&device);
+        unnamed.destroy_self(&device);
+        unit.destroy_self(&device);
+        owned.destroy_self(&device);
+    }
+
+    if let Resource::Named { a, b, c } = &named {
+        a.assert_destroyed();
+        b.assert_not_destroyed();
+        c.assert_destroyed();
+    }
+    if let Resource::Unnamed(a, b, _) = &unnamed {
+        a.assert_destroyed();
+        b.assert_not_destroyed();
+    }
+    if let Generic::Owned {
+        device,
+        allocation_callbacks,
+    } = &owned
+    {
+        device.assert_destroyed();
+        allocation_callbacks.as_ref().unwrap().assert_destroyed();
+    }
+
+    let borrowed_value = ImplDeviceDestroyable::new();
+    let borrowed = Generic::Borrowed(&borrowed_value);
+    unsafe {
+        borrowed.destroy_self(&device);
+    }
+    borrowed_value.assert_destroyed();
+}
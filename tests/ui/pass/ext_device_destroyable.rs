@@ -0,0 +1,77 @@
+use std::cell::Cell;
+
+use ash::vk::{self, Handle as _};
+use ash_destructor::{
+    testing::{DestroyCall, MockDevice},
+    ExtDeviceDestroyable,
+};
+
+struct Loader {
+    destroyed: Cell<u32>,
+}
+
+struct Handle;
+
+impl ExtDeviceDestroyable<Loader> for Handle {
+    unsafe fn destroy_self_alloc(&self, loader: &Loader, _: Option<&ash::vk::AllocationCallbacks<'_>>) {
+        loader.destroyed.set(loader.destroyed.get() + 1);
+    }
+}
+
+// destroys a handle through the loader of its extension and checks the call reached the mock
+macro_rules! assert_ext_destroy {
+    ($mock:expr, $callbacks:expr, $($loader:ty => $handle:ty),* $(,)?) => {$(
+        let loader = <$loader>::new($mock.instance(), $mock.device());
+        let handle = <$handle>::from_raw(0x1000 + <$handle as vk::Handle>::TYPE.as_raw() as u64);
+        unsafe {
+            handle.destroy_self_alloc(&loader, Some($callbacks));
+        }
+        assert_eq!($mock.take_calls(), [DestroyCall::new(handle, Some($callbacks))]);
+    )*};
+}
+
+fn main() {
+    let loader = Loader {
+        destroyed: Cell::new(0),
+    };
+    let handles = (vec![Handle, Handle], [Some(Handle), None], Box::new(Handle));
+    unsafe {
+        handles.0.destroy_self(&loader);
+        handles.1.destroy_self(&loader);
+        handles.2.destroy_self(&loader);
+    }
+    assert_eq!(loader.destroyed.get(), 4);
+
+    let mock = MockDevice::new();
+    let callbacks = vk::AllocationCallbacks::default();
+    assert_ext_destroy! {
+        mock, &callbacks,
+        ash::khr::swapchain::Device => vk::SwapchainKHR,
+        ash::khr::acceleration_structure::Device => vk::AccelerationStructureKHR,
+        ash::khr::deferred_host_operations::Device => vk::DeferredOperationKHR,
+        ash::khr::video_queue::Device => vk::VideoSessionKHR,
+        ash::khr::video_queue::Device => vk::VideoSessionParametersKHR,
+        ash::khr::descriptor_update_template::Device => vk::DescriptorUpdateTemplate,
+        ash::khr::sampler_ycbcr_conversion::Device => vk::SamplerYcbcrConversion,
+        ash::ext::private_data::Device => vk::PrivateDataSlot,
+        ash::ext::opacity_micromap::Device => vk::MicromapEXT,
+        ash::ext::shader_object::Device => vk::ShaderEXT,
+        ash::ext::validation_cache::Device => vk::ValidationCacheEXT,
+        ash::nv::ray_tracing::Device => vk::AccelerationStructureNV,
+        ash::nv::device_generated_commands::Device => vk::IndirectCommandsLayoutNV,
+        ash::nv::optical_flow::Device => vk::OpticalFlowSessionNV,
+        ash::nv::cuda_kernel_launch::Device => vk::CudaModuleNV,
+        ash::nv::cuda_kernel_launch::Device => vk::CudaFunctionNV,
+        ash::nvx::binary_import::Device => vk::CuModuleNVX,
+        ash::nvx::binary_import::Device => vk::CuFunctionNVX,
+        ash::fuchsia::buffer_collection::Device => vk::BufferCollectionFUCHSIA,
+    }
+
+    // performance configurations are released without allocation callbacks
+    let loader = ash::intel::performance_query::Device::new(mock.instance(), mock.device());
+    let configuration = vk::PerformanceConfigurationINTEL::from_raw(1);
+    unsafe {
+        configuration.destroy_self_alloc(&loader, Some(&callbacks));
+    }
+    assert_eq!(mock.take_calls(), [DestroyCall::new(configuration, None)]);
+}
@@ -0,0 +1,129 @@
+use std::ffi::c_void;
+
+use ash::vk::{self, Handle};
+use ash_destructor::{
+    testing::{DestroyCall, MockDevice},
+    Alloc, DeviceDestroyable, HostAllocationTracker,
+};
+
+// stands in for an object whose driver host memory is freed through the callbacks it is destroyed with
+struct TrackedImage {
+    image: vk::Image,
+    memory: *mut c_void,
+}
+
+impl TrackedImage {
+    fn new(callbacks: &vk::AllocationCallbacks, id: u64) -> Self {
+        let alloc = callbacks.pfn_allocation.unwrap();
+        Self {
+            image: vk::Image::from_raw(id),
+            memory: unsafe { alloc(callbacks.p_user_data, 64, 8, vk::SystemAllocationScope::OBJECT) },
+        }
+    }
+}
+
+impl DeviceDestroyable for TrackedImage {
+    unsafe fn destroy_self_alloc(&self, device: &ash::Device, allocation_callbacks: Alloc) {
+        self.image.destroy_self(device);
+        let callbacks = allocation_callbacks.expect("tracked images are destroyed with their callbacks");
+        (callbacks.pfn_free.unwrap())(callbacks.p_user_data, self.memory);
+    }
+}
+
+// created with the default allocator
+struct DefaultPipeline(vk::Pipeline);
+
+impl DeviceDestroyable for DefaultPipeline {
+    unsafe fn destroy_self_alloc(&self, device: &ash::Device, allocation_callbacks: Alloc) {
+        assert!(allocation_callbacks.is_none());
+        self.0.destroy_self_alloc(device, allocation_callbacks);
+    }
+}
+
+#[derive(DeviceDestroyable)]
+struct Renderer {
+    #[destroy(alloc = self.tracker)]
+    color: TrackedImage,
+    #[destroy(alloc = self.tracker)]
+    depth: TrackedImage,
+    #[destroy(alloc = self.tracker)]
+    image: vk::Image,
+    #[destroy(alloc = none)]
+    pipeline: DefaultPipeline,
+    buffer: vk::Buffer,
+    #[destroy_ignore]
+    tracker: Box<HostAllocationTracker>,
+}
+
+#[derive(DeviceDestroyable)]
+enum Target {
+    Offscreen {
+        #[destroy(alloc = self.callbacks)]
+        image: TrackedImage,
+        #[destroy_ignore]
+        callbacks: Option<vk::AllocationCallbacks<'static>>,
+    },
+    Default(#[destroy(alloc = none)] DefaultPipeline),
+}
+
+fn main() {
+    let mock = MockDevice::new();
+    let tracker = Box::new(HostAllocationTracker::new());
+    let callbacks = tracker.callbacks();
+    let renderer = Renderer {
+        color: TrackedImage::new(&callbacks, 1),
+        depth: TrackedImage::new(&callbacks, 2),
+        image: vk::Image::from_raw(7),
+        pipeline: DefaultPipeline(vk::Pipeline::from_raw(3)),
+        buffer: vk::Buffer::from_raw(4),
+        tracker,
+    };
+    assert_eq!(renderer.tracker.total_live_allocations(), 2);
+
+    // the fields without an attribute still receive the given callbacks
+    let given = vk::AllocationCallbacks::default();
+    unsafe {
+        renderer.destroy_self_alloc(&mock, Some(&given));
+    }
+    assert_eq!(renderer.tracker.total_live_allocations(), 0);
+    let calls = mock.take_calls();
+    assert_eq!(
+        calls[..2],
+        [
+            DestroyCall::new(vk::Buffer::from_raw(4), Some(&given)),
+            DestroyCall::new(vk::Pipeline::from_raw(3), None),
+        ]
+    );
+    // the callbacks of the tracker are built for the call, so they are recognized by their user data
+    assert_eq!((calls[2].object_type, calls[2].handle), (vk::ObjectType::IMAGE, 7));
+    assert!(!calls[2].allocation_callbacks.is_null());
+    assert_eq!(calls[2].allocation_user_data, renderer.tracker.callbacks().p_user_data);
+    assert_eq!(
+        calls[3..],
+        [
+            DestroyCall::new(vk::Image::from_raw(2), None),
+            DestroyCall::new(vk::Image::from_raw(1), None),
+        ]
+    );
+
+    static TRACKER: std::sync::LazyLock<HostAllocationTracker> = std::sync::LazyLock::new(HostAllocationTracker::new);
+    let callbacks = TRACKER.callbacks();
+    let targets = [
+        Target::Offscreen {
+            image: TrackedImage::new(&callbacks, 5),
+            callbacks: Some(callbacks),
+        },
+        Target::Default(DefaultPipeline(vk::Pipeline::from_raw(6))),
+    ];
+    unsafe {
+        targets.destroy_self(&mock);
+    }
+    assert_eq!(TRACKER.total_live_allocations(), 0);
+    assert_eq!(
+        mock.take_calls(),
+        [
+            DestroyCall::new(vk::Pipeline::from_raw(6), None),
+            DestroyCall::new(vk::Image::from_raw(5), None),
+        ]
+    );
+}
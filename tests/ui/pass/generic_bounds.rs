@@ -0,0 +1,95 @@
+use std::{collections::HashMap, marker::PhantomData};
+
+use ash::vk::{self, Handle};
+use ash_destructor::{
+    testing::{DestructionRecorder, MockDevice},
+    DeviceDestroyable, InstanceDestroyable, SelfDestroyable,
+};
+
+// the bounds are inferred from the fields
+#[derive(DeviceDestroyable)]
+struct Wrapper<T> {
+    inner: T,
+}
+
+#[derive(DeviceDestroyable)]
+struct Cache<K, V> {
+    entries: HashMap<K, V>,
+    #[destroy_ignore]
+    fallback: Option<V>,
+}
+
+#[derive(DeviceDestroyable)]
+enum Either<L, R> {
+    Left(L),
+    Right { right: Vec<R> },
+}
+
+#[derive(InstanceDestroyable)]
+struct Surfaces<S> {
+    surfaces: Vec<S>,
+}
+
+#[derive(SelfDestroyable)]
+struct Context<T> {
+    #[destroy_device]
+    device: ash::Device,
+    resources: Wrapper<T>,
+}
+
+// ignored fields don't need to be destroyable
+#[derive(DeviceDestroyable)]
+struct Tagged<T, Tag> {
+    value: T,
+    #[destroy_ignore]
+    tag: PhantomData<Tag>,
+}
+
+// the inferred bounds are replaced by the given ones
+#[derive(DeviceDestroyable)]
+#[destroy(bound = "T: Copy + DeviceDestroyable")]
+struct Frames<T> {
+    frames: Vec<T>,
+}
+
+#[derive(DeviceDestroyable)]
+#[destroy(bound = "")]
+struct Handles<T: Into<u64>> {
+    #[destroy_ignore]
+    raw: Vec<T>,
+    image: vk::Image,
+}
+
+fn destroy<T: DeviceDestroyable>(value: T, device: &ash::Device) {
+    unsafe { value.destroy(device) };
+}
+
+fn main() {
+    let mock = MockDevice::new();
+    let recorder = unsafe { DestructionRecorder::wrap(&mock) };
+
+    destroy(Wrapper { inner: vk::Buffer::from_raw(1) }, &recorder);
+    destroy(
+        Cache {
+            entries: HashMap::from([("shadow", vk::Pipeline::from_raw(2))]),
+            fallback: Some(vk::Pipeline::from_raw(3)),
+        },
+        &recorder,
+    );
+    destroy(Either::<vk::Fence, vk::Semaphore>::Left(vk::Fence::from_raw(4)), &recorder);
+    destroy(Tagged::<_, String> { value: vk::Event::from_raw(5), tag: PhantomData }, &recorder);
+    destroy(Frames { frames: vec![vk::Sampler::from_raw(6)] }, &recorder);
+    destroy(Handles { raw: vec![7u64], image: vk::Image::from_raw(8) }, &recorder);
+
+    let destroyed = recorder
+        .entries()
+        .iter()
+        .map(|entry| entry.handle)
+        .collect::<Vec<_>>();
+    assert_eq!(destroyed, [1, 2, 4, 5, 6, 8]);
+
+    let _ = |surfaces: Surfaces<vk::SurfaceKHR>, loaders: &ash_destructor::InstanceLoaders| unsafe {
+        surfaces.destroy(loaders)
+    };
+    let _ = |context: Context<vk::Image>| unsafe { SelfDestroyable::destroy(context) };
+}
@@ -0,0 +1,115 @@
+use std::{
+    alloc::Layout,
+    ffi::c_void,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use ash::vk::{self, Handle};
+use ash_destructor::{
+    testing::{DestroyCall, MockDevice},
+    Alloc, DeviceDestroyable, HostAllocationTracker,
+};
+
+// stands in for an object whose driver host memory is freed when it is destroyed
+struct DriverObject {
+    image: vk::Image,
+    memory: *mut c_void,
+}
+
+impl DeviceDestroyable for DriverObject {
+    unsafe fn destroy_self_alloc(&self, device: &ash::Device, allocation_callbacks: Alloc) {
+        self.image.destroy_self_alloc(device, allocation_callbacks);
+        if let Some(callbacks) = allocation_callbacks {
+            (callbacks.pfn_free.unwrap())(callbacks.p_user_data, self.memory);
+        }
+    }
+}
+
+unsafe fn create(callbacks: &vk::AllocationCallbacks, id: u64, scope: vk::SystemAllocationScope) -> DriverObject {
+    DriverObject {
+        image: vk::Image::from_raw(id),
+        memory: (callbacks.pfn_allocation.unwrap())(callbacks.p_user_data, 64, 16, scope),
+    }
+}
+
+fn main() {
+    let mock = MockDevice::new();
+    let tracker = HostAllocationTracker::new();
+    let callbacks = tracker.callbacks();
+
+    let object = unsafe { create(&callbacks, 1, vk::SystemAllocationScope::OBJECT) };
+    let cache = unsafe { create(&callbacks, 2, vk::SystemAllocationScope::CACHE) };
+    assert_eq!(object.memory as usize % 16, 0);
+    assert_eq!(tracker.live_allocations(vk::SystemAllocationScope::OBJECT), 1);
+    assert_eq!(tracker.live_allocations(vk::SystemAllocationScope::CACHE), 1);
+    assert_eq!(tracker.live_allocations(vk::SystemAllocationScope::COMMAND), 0);
+    assert_eq!(tracker.live_bytes(), 128);
+
+    // reallocating keeps the contents and moves the allocation to its new scope
+    let grown = unsafe {
+        object.memory.cast::<u8>().write_bytes(7, 64);
+        (callbacks.pfn_reallocation.unwrap())(
+            callbacks.p_user_data,
+            object.memory,
+            256,
+            16,
+            vk::SystemAllocationScope::DEVICE,
+        )
+    };
+    let object = DriverObject { memory: grown, ..object };
+    assert!(unsafe { std::slice::from_raw_parts(grown.cast::<u8>(), 64) }.iter().all(|&byte| byte == 7));
+    assert_eq!(tracker.live_allocations(vk::SystemAllocationScope::OBJECT), 0);
+    assert_eq!(tracker.live_allocations(vk::SystemAllocationScope::DEVICE), 1);
+    assert_eq!(tracker.live_bytes(), 320);
+
+    unsafe {
+        [object, cache].destroy_self_alloc(&mock, Some(&callbacks));
+    }
+    assert_eq!(
+        mock.take_calls(),
+        [
+            DestroyCall::new(vk::Image::from_raw(2), Some(&callbacks)),
+            DestroyCall::new(vk::Image::from_raw(1), Some(&callbacks)),
+        ]
+    );
+    assert_eq!(tracker.total_live_allocations(), 0);
+    assert_eq!(tracker.live_bytes(), 0);
+
+    // allocations can go through closures instead of the global allocator
+    let allocated = Arc::new(AtomicUsize::new(0));
+    let freed = Arc::new(AtomicUsize::new(0));
+    let tracker = HostAllocationTracker::with_allocator(
+        {
+            let allocated = allocated.clone();
+            move |layout: Layout| {
+                allocated.fetch_add(1, Ordering::Relaxed);
+                unsafe { std::alloc::alloc(layout) }
+            }
+        },
+        {
+            let freed = freed.clone();
+            move |memory, layout| {
+                freed.fetch_add(1, Ordering::Relaxed);
+                unsafe { std::alloc::dealloc(memory, layout) }
+            }
+        },
+    );
+    let callbacks = tracker.callbacks();
+    let instance_object = unsafe { create(&callbacks, 3, vk::SystemAllocationScope::INSTANCE) };
+    let object = unsafe { create(&callbacks, 4, vk::SystemAllocationScope::OBJECT) };
+    unsafe {
+        object.destroy_self_alloc(&mock, Some(&callbacks));
+    }
+    assert_eq!(allocated.load(Ordering::Relaxed), 2);
+    assert_eq!(freed.load(Ordering::Relaxed), 1);
+    assert_eq!(tracker.live_allocations(vk::SystemAllocationScope::INSTANCE), 1);
+    assert_eq!(tracker.total_live_allocations(), 1);
+
+    unsafe {
+        instance_object.destroy_self_alloc(&mock, Some(&callbacks));
+    }
+    assert_eq!(tracker.total_live_allocations(), 0);
+}
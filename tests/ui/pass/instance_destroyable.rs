@@ -0,0 +1,29 @@
+use ash::vk;
+use ash_destructor::{InstanceDestroyable, InstanceLoaders};
+
+#[derive(InstanceDestroyable)]
+struct VulkanContext {
+    #[destroy_ignore]
+    pub entry: ash::Entry,
+    pub instance: ash::Instance,
+    #[destroy_ignore]
+    pub loaders: InstanceLoaders,
+    pub debug_messenger: Option<vk::DebugUtilsMessengerEXT>,
+    pub surfaces: Vec<vk::SurfaceKHR>,
+    pub device: ash::Device,
+}
+
+#[derive(InstanceDestroyable)]
+enum Debug {
+    Utils(vk::DebugUtilsMessengerEXT),
+    Report { callback: vk::DebugReportCallbackEXT },
+    Disabled,
+}
+
+#[allow(dead_code)]
+unsafe fn destroy(context: &VulkanContext, debug: &Debug) {
+    debug.destroy_self(&context.loaders);
+    context.destroy_self(&context.loaders);
+}
+
+fn main() {}
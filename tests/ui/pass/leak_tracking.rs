@@ -0,0 +1,55 @@
+use ash::vk::{self, Handle};
+use ash_destructor::{
+    leak_tracking::{self, LeakedHandle},
+    testing::MockDevice,
+    DeviceDestroyable,
+};
+
+#[derive(DeviceDestroyable)]
+struct Attachment {
+    image: vk::Image,
+    #[destroy_ignore]
+    view: vk::ImageView,
+}
+
+fn main() {
+    let _report = leak_tracking::report_at_exit();
+    let mock = MockDevice::new();
+    let device = mock.device().handle();
+    let attachment = Attachment {
+        image: leak_tracking::track(device, vk::Image::from_raw(1)),
+        view: leak_tracking::track(device, vk::ImageView::from_raw(2)),
+    };
+    let buffer = leak_tracking::track(device, vk::Buffer::from_raw(3));
+    // another device handing out the same raw value
+    let other = MockDevice::new();
+    let other_buffer = leak_tracking::track(other.device().handle(), vk::Buffer::from_raw(3));
+    assert_eq!(leak_tracking::leaked().len(), 4);
+
+    unsafe {
+        attachment.destroy_self(&mock);
+        buffer.destroy_self(&mock);
+        // untracked handles are ignored
+        vk::Sampler::from_raw(4).destroy_self(&mock);
+    }
+    let mut leaked = leak_tracking::leaked();
+    leaked.sort_by_key(|leak| (leak.object_type.as_raw(), leak.handle));
+    assert_eq!(
+        leaked.iter().map(|leak| (leak.parent, leak.object_type, leak.handle)).collect::<Vec<_>>(),
+        [
+            (other.device().handle().as_raw(), vk::ObjectType::BUFFER, 3),
+            (device.as_raw(), vk::ObjectType::IMAGE_VIEW, 2),
+        ]
+    );
+    let report = leak_tracking::report().unwrap();
+    assert!(
+        report.contains(&format!("IMAGE_VIEW 0x2 created through 0x{:x}", device.as_raw())),
+        "{report}"
+    );
+
+    unsafe { other_buffer.destroy_self(&other) };
+    assert!(leak_tracking::untrack(device, attachment.view));
+    assert!(!leak_tracking::untrack(device, attachment.view));
+    assert_eq!(leak_tracking::leaked(), Vec::<LeakedHandle>::new());
+    assert_eq!(leak_tracking::report(), None);
+}
@@ -0,0 +1,72 @@
+use std::sync::Mutex;
+
+use ash::vk::{self, Handle};
+use ash_destructor::{testing::MockDevice, DeviceDestroyable, InstanceDestroyable};
+
+static MESSAGES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+struct Capture;
+
+impl log::Log for Capture {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.target() == "ash_destructor"
+    }
+
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            MESSAGES.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+#[derive(DeviceDestroyable)]
+struct Texture {
+    image: vk::Image,
+    views: Vec<vk::ImageView>,
+}
+
+#[derive(DeviceDestroyable)]
+enum Resource {
+    Buffer { buffer: vk::Buffer },
+}
+
+fn main() {
+    log::set_logger(&Capture).unwrap();
+    log::set_max_level(log::LevelFilter::Debug);
+
+    let mock = MockDevice::new();
+    let device = mock.handle().as_raw();
+    let texture = Texture {
+        image: vk::Image::from_raw(1),
+        views: vec![vk::ImageView::from_raw(2), vk::ImageView::from_raw(3)],
+    };
+    let resource = Resource::Buffer {
+        buffer: vk::Buffer::from_raw(4),
+    };
+    unsafe {
+        texture.destroy_self(&mock);
+        resource.destroy_self(&mock);
+        vk::Sampler::from_raw(5).destroy_self(&mock);
+        (vk::Fence::from_raw(6), [vk::Semaphore::from_raw(7)]).destroy_self(&mock);
+        let surfaces = [vk::SurfaceKHR::from_raw(8), vk::SurfaceKHR::from_raw(9)];
+        InstanceDestroyable::destroy_self(&surfaces, &mock.instance_loaders());
+    }
+    let instance = mock.instance().handle().as_raw();
+
+    assert_eq!(
+        *MESSAGES.lock().unwrap(),
+        [
+            format!("destroying IMAGE_VIEW 0x3 through 0x{device:x} at Texture::views[1]"),
+            format!("destroying IMAGE_VIEW 0x2 through 0x{device:x} at Texture::views[0]"),
+            format!("destroying IMAGE 0x1 through 0x{device:x} at Texture::image"),
+            format!("destroying BUFFER 0x4 through 0x{device:x} at Resource::Buffer.buffer"),
+            format!("destroying SAMPLER 0x5 through 0x{device:x}"),
+            format!("destroying SEMAPHORE 0x7 through 0x{device:x} at [1][0]"),
+            format!("destroying FENCE 0x6 through 0x{device:x} at [0]"),
+            format!("destroying SURFACE_KHR 0x9 through 0x{instance:x} at [1]"),
+            format!("destroying SURFACE_KHR 0x8 through 0x{instance:x} at [0]"),
+        ]
+    );
+}
@@ -0,0 +1,56 @@
+use ash::vk::{self, Handle};
+use ash_destructor::{testing::{DestroyCall, MockDevice}, DeviceDestroyable, InstanceDestroyable, SelfDestroyable};
+
+#[derive(DeviceDestroyable)]
+struct Texture {
+    memory: vk::DeviceMemory,
+    image: vk::Image,
+    views: Vec<vk::ImageView>,
+}
+
+fn main() {
+    let mock = MockDevice::new();
+    let texture = Texture {
+        memory: vk::DeviceMemory::from_raw(1),
+        image: vk::Image::from_raw(2),
+        views: vec![vk::ImageView::from_raw(3), vk::ImageView::from_raw(4)],
+    };
+    let allocation_callbacks = vk::AllocationCallbacks::default();
+    unsafe {
+        texture.destroy_self(&mock);
+        texture.destroy_self_alloc(&mock, Some(&allocation_callbacks));
+    }
+
+    let expected = [
+        DestroyCall::new(vk::ImageView::from_raw(4), None),
+        DestroyCall::new(vk::ImageView::from_raw(3), None),
+        DestroyCall::new(vk::Image::from_raw(2), None),
+        DestroyCall::new(vk::DeviceMemory::from_raw(1), None),
+    ];
+    let calls = mock.take_calls();
+    assert_eq!(calls[..4], expected);
+    assert_eq!(calls[4].object_type, vk::ObjectType::IMAGE_VIEW);
+    assert_eq!(calls[4].allocation_callbacks, (&allocation_callbacks as *const vk::AllocationCallbacks).cast());
+    assert_eq!(calls.len(), 8);
+    assert!(mock.calls().is_empty());
+
+    let loaders = mock.instance_loaders();
+    unsafe {
+        vk::SurfaceKHR::from_raw(5).destroy_self(&loaders);
+        SelfDestroyable::destroy_self(mock.device());
+        SelfDestroyable::destroy_self(mock.instance());
+    }
+    let calls = mock.take_calls();
+    assert_eq!(calls[0], DestroyCall::new(vk::SurfaceKHR::from_raw(5), None));
+    assert_eq!(calls[1].object_type, vk::ObjectType::DEVICE);
+    assert_eq!(calls[1].handle, mock.device().handle().as_raw());
+    assert_eq!(calls[2].object_type, vk::ObjectType::INSTANCE);
+
+    // logs are kept per mock
+    let other = MockDevice::new();
+    unsafe {
+        vk::Buffer::from_raw(6).destroy_self(&other);
+    }
+    assert!(mock.calls().is_empty());
+    assert_eq!(other.calls(), [DestroyCall::new(vk::Buffer::from_raw(6), None)]);
+}
@@ -0,0 +1,173 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Mutex,
+};
+
+use ash::vk::{self, Handle};
+use ash_destructor::{
+    testing::{DestroyCall, MockDevice},
+    DeviceDestroyable, PooledCommandBuffers, SetNull,
+};
+
+// stands in for a third-party type that cannot implement SetNull
+mod allocator {
+    use ash::vk;
+
+    pub struct Allocation {
+        pub memory: vk::DeviceMemory,
+    }
+
+    pub unsafe fn free(
+        allocation: &Allocation,
+        device: &ash::Device,
+        allocation_callbacks: Option<&vk::AllocationCallbacks>,
+    ) {
+        device.free_memory(allocation.memory, allocation_callbacks);
+    }
+
+    pub fn reset(allocation: &mut Allocation) {
+        allocation.memory = vk::DeviceMemory::null();
+    }
+}
+
+#[derive(DeviceDestroyable, SetNull)]
+struct Attachment {
+    image: vk::Image,
+    views: Vec<vk::ImageView>,
+    #[destroy_ignore]
+    format: vk::Format,
+}
+
+#[derive(DeviceDestroyable, SetNull)]
+enum Target {
+    Offscreen(Attachment),
+    Swapchain {
+        #[destroy_ignore]
+        image: vk::Image,
+        view: vk::ImageView,
+    },
+}
+
+#[derive(DeviceDestroyable, SetNull)]
+struct Renderer<T> {
+    target: T,
+    commands: PooledCommandBuffers,
+    #[destroy_ignore_remaining]
+    frame: u64,
+}
+
+#[derive(DeviceDestroyable, SetNull)]
+struct Buffer {
+    #[destroy_with = "allocator::free"]
+    #[destroy(null_with = none)]
+    allocation: allocator::Allocation,
+    #[destroy_with = "allocator::free"]
+    #[destroy(null_with = "allocator::reset")]
+    staging: allocator::Allocation,
+    buffer: vk::Buffer,
+}
+
+#[derive(DeviceDestroyable, SetNull)]
+struct Pipelines {
+    pipelines: HashMap<u32, vk::Pipeline>,
+    layouts: BTreeMap<u32, vk::PipelineLayout>,
+    attachment: (vk::Image, vk::ImageView),
+    fence: Mutex<vk::Fence>,
+}
+
+fn main() {
+    let mock = MockDevice::new();
+
+    // partially initialized values only destroy their initialized handles
+    let partial = Attachment {
+        image: vk::Image::from_raw(1),
+        views: vec![vk::ImageView::null(), vk::ImageView::from_raw(2)],
+        format: vk::Format::R8G8B8A8_SRGB,
+    };
+    unsafe { partial.destroy_self(&mock) };
+    assert_eq!(
+        mock.take_calls(),
+        [
+            DestroyCall::new(vk::ImageView::from_raw(2), None),
+            DestroyCall::new(vk::Image::from_raw(1), None),
+        ]
+    );
+
+    let mut renderer = Renderer {
+        target: Target::Offscreen(Attachment {
+            image: vk::Image::from_raw(3),
+            views: vec![vk::ImageView::from_raw(4)],
+            format: vk::Format::R8G8B8A8_SRGB,
+        }),
+        commands: PooledCommandBuffers {
+            pool: vk::CommandPool::from_raw(5),
+            buffers: vec![vk::CommandBuffer::from_raw(6)],
+        },
+        frame: 7,
+    };
+    unsafe { renderer.destroy_and_null(&mock) };
+    assert_eq!(
+        mock.take_calls(),
+        [
+            DestroyCall::new(vk::CommandBuffer::from_raw(6), None),
+            DestroyCall::new(vk::ImageView::from_raw(4), None),
+            DestroyCall::new(vk::Image::from_raw(3), None),
+        ]
+    );
+    let Target::Offscreen(attachment) = &renderer.target else {
+        unreachable!()
+    };
+    assert_eq!(attachment.image, vk::Image::null());
+    assert_eq!(attachment.views, [vk::ImageView::null()]);
+    assert_eq!(attachment.format, vk::Format::R8G8B8A8_SRGB);
+    assert_eq!(renderer.commands.pool, vk::CommandPool::from_raw(5));
+    assert_eq!(renderer.commands.buffers, [vk::CommandBuffer::null()]);
+    assert_eq!(renderer.frame, 7);
+
+    // destroying again skips the nulled handles
+    unsafe { renderer.destroy_self(&mock) };
+    assert_eq!(mock.take_calls(), []);
+
+    let mut swapchain = Target::Swapchain {
+        image: vk::Image::from_raw(8),
+        view: vk::ImageView::from_raw(9),
+    };
+    unsafe { swapchain.destroy_and_null(&mock) };
+    assert_eq!(mock.take_calls(), [DestroyCall::new(vk::ImageView::from_raw(9), None)]);
+    let Target::Swapchain { image, view } = swapchain else {
+        unreachable!()
+    };
+    assert_eq!((image, view), (vk::Image::from_raw(8), vk::ImageView::null()));
+
+    // fields destroyed with a function are reset with #[destroy(null_with = "...")], or left untouched
+    // when opted out
+    let mut buffer = Buffer {
+        allocation: allocator::Allocation {
+            memory: vk::DeviceMemory::from_raw(10),
+        },
+        staging: allocator::Allocation {
+            memory: vk::DeviceMemory::from_raw(11),
+        },
+        buffer: vk::Buffer::from_raw(12),
+    };
+    unsafe { buffer.destroy_and_null(&mock) };
+    assert_eq!(buffer.allocation.memory, vk::DeviceMemory::from_raw(10));
+    assert_eq!(buffer.staging.memory, vk::DeviceMemory::null());
+    assert_eq!(buffer.buffer, vk::Buffer::null());
+    mock.take_calls();
+
+    let mut pipelines = Pipelines {
+        pipelines: HashMap::from([(0, vk::Pipeline::from_raw(13))]),
+        layouts: BTreeMap::from([(0, vk::PipelineLayout::from_raw(14))]),
+        attachment: (vk::Image::from_raw(15), vk::ImageView::from_raw(16)),
+        fence: Mutex::new(vk::Fence::from_raw(17)),
+    };
+    unsafe { pipelines.destroy_and_null(&mock) };
+    assert_eq!(mock.take_calls().len(), 5);
+    assert_eq!(pipelines.pipelines[&0], vk::Pipeline::null());
+    assert_eq!(pipelines.layouts[&0], vk::PipelineLayout::null());
+    assert_eq!(pipelines.attachment, (vk::Image::null(), vk::ImageView::null()));
+    assert_eq!(*pipelines.fence.lock().unwrap(), vk::Fence::null());
+    unsafe { pipelines.destroy_self(&mock) };
+    assert_eq!(mock.take_calls(), []);
+}
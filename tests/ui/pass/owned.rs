@@ -0,0 +1,46 @@
+use ash_destructor::Owned;
+
+#[path = "../../utils/mod.rs"]
+mod utils;
+
+use utils::ImplDeviceDestroyable;
+
+fn early_return<'a>(
+    device: &ash::Device,
+    values: &'a [ImplDeviceDestroyable],
+    fail: bool,
+) -> Result<Owned<Vec<&'a ImplDeviceDestroyable>>, ()> {
+    let values = unsafe { Owned::new(device, values.iter().collect()) };
+    if fail {
+        return Err(());
+    }
+    Ok(values)
+}
+
+fn main() {
+    let device = utils::create_dummy_device();
+
+    let value = ImplDeviceDestroyable::new();
+    {
+        let owned = unsafe { Owned::new(&device, &value) };
+        owned.assert_not_destroyed();
+    }
+    value.assert_destroyed();
+
+    let owned = unsafe { Owned::new(&device, ImplDeviceDestroyable::new()) };
+    let value = Owned::into_inner(owned);
+    value.assert_not_destroyed();
+
+    let owned = unsafe { Owned::new(&device, ImplDeviceDestroyable::new()) };
+    let leaked: &'static mut ImplDeviceDestroyable = Owned::leak(owned);
+    leaked.assert_not_destroyed();
+
+    let kept = [ImplDeviceDestroyable::new(), ImplDeviceDestroyable::new()];
+    let values = Owned::into_inner(early_return(&device, &kept, false).unwrap());
+    assert_eq!(values.len(), 2);
+    kept.iter().for_each(ImplDeviceDestroyable::assert_not_destroyed);
+
+    let failed = [ImplDeviceDestroyable::new(), ImplDeviceDestroyable::new()];
+    assert!(early_return(&device, &failed, true).is_err());
+    failed.iter().for_each(ImplDeviceDestroyable::assert_destroyed);
+}
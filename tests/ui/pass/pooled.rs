@@ -0,0 +1,90 @@
+use ash::vk::{self, Handle};
+use ash_destructor::{
+    testing::{DestroyCall, MockDevice},
+    DeviceDestroyable, PooledCommandBuffers, PooledDescriptorSets,
+};
+
+#[derive(DeviceDestroyable)]
+struct Frames {
+    #[destroy(pool = command_pool)]
+    command_buffers: Vec<vk::CommandBuffer>,
+    command_pool: vk::CommandPool,
+    #[destroy(pool = descriptor_pool)]
+    descriptor_sets: [vk::DescriptorSet; 2],
+    #[destroy_ignore]
+    descriptor_pool: vk::DescriptorPool,
+}
+
+#[derive(DeviceDestroyable)]
+enum Recording {
+    Pending(vk::CommandPool, #[destroy(pool = 0)] Vec<vk::CommandBuffer>),
+    Shared {
+        #[destroy_ignore]
+        pool: vk::CommandPool,
+        #[destroy(pool = pool)]
+        buffers: Vec<vk::CommandBuffer>,
+    },
+}
+
+fn main() {
+    let mock = MockDevice::new();
+    let command_buffers = PooledCommandBuffers {
+        pool: vk::CommandPool::from_raw(1),
+        buffers: vec![vk::CommandBuffer::from_raw(2), vk::CommandBuffer::from_raw(3)],
+    };
+    let descriptor_sets = PooledDescriptorSets {
+        pool: vk::DescriptorPool::from_raw(4),
+        sets: vec![vk::DescriptorSet::from_raw(5)],
+    };
+    unsafe {
+        command_buffers.destroy_self(&mock);
+        descriptor_sets.destroy_self(&mock);
+        PooledCommandBuffers::default().destroy_self(&mock);
+    }
+    assert_eq!(
+        mock.take_calls(),
+        [
+            DestroyCall::new(vk::CommandBuffer::from_raw(2), None),
+            DestroyCall::new(vk::CommandBuffer::from_raw(3), None),
+            DestroyCall::new(vk::DescriptorSet::from_raw(5), None),
+        ]
+    );
+
+    // handles are freed before their pool even though they are declared first
+    let frames = Frames {
+        command_buffers: vec![vk::CommandBuffer::from_raw(6)],
+        command_pool: vk::CommandPool::from_raw(7),
+        descriptor_sets: [vk::DescriptorSet::from_raw(8), vk::DescriptorSet::from_raw(9)],
+        descriptor_pool: vk::DescriptorPool::from_raw(10),
+    };
+    unsafe {
+        frames.destroy_self(&mock);
+    }
+    assert_eq!(
+        mock.take_calls(),
+        [
+            DestroyCall::new(vk::DescriptorSet::from_raw(8), None),
+            DestroyCall::new(vk::DescriptorSet::from_raw(9), None),
+            DestroyCall::new(vk::CommandBuffer::from_raw(6), None),
+            DestroyCall::new(vk::CommandPool::from_raw(7), None),
+        ]
+    );
+
+    let pending = Recording::Pending(vk::CommandPool::from_raw(11), vec![vk::CommandBuffer::from_raw(12)]);
+    let shared = Recording::Shared {
+        pool: vk::CommandPool::from_raw(13),
+        buffers: vec![vk::CommandBuffer::from_raw(14)],
+    };
+    unsafe {
+        pending.destroy_self(&mock);
+        shared.destroy_self(&mock);
+    }
+    assert_eq!(
+        mock.take_calls(),
+        [
+            DestroyCall::new(vk::CommandBuffer::from_raw(12), None),
+            DestroyCall::new(vk::CommandPool::from_raw(11), None),
+            DestroyCall::new(vk::CommandBuffer::from_raw(14), None),
+        ]
+    );
+}
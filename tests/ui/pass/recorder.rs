@@ -0,0 +1,93 @@
+use ash::vk::{self, Handle};
+use ash_destructor::{
+    testing::{DestructionEntry, DestructionRecorder, MockDevice},
+    DeviceDestroyable,
+};
+
+#[derive(DeviceDestroyable)]
+struct Attachment {
+    image: vk::Image,
+    view: vk::ImageView,
+}
+
+#[derive(DeviceDestroyable)]
+enum Target {
+    Offscreen(Attachment),
+    #[allow(dead_code)]
+    Swapchain,
+}
+
+#[derive(DeviceDestroyable)]
+struct Renderer {
+    render_pass: vk::RenderPass,
+    attachments: Vec<Attachment>,
+    target: Target,
+}
+
+fn main() {
+    let mock = MockDevice::new();
+    let recorder = unsafe { DestructionRecorder::wrap(&mock) };
+
+    let renderer = Renderer {
+        render_pass: vk::RenderPass::from_raw(1),
+        attachments: vec![
+            Attachment {
+                image: vk::Image::from_raw(2),
+                view: vk::ImageView::from_raw(3),
+            },
+            Attachment {
+                image: vk::Image::from_raw(4),
+                view: vk::ImageView::from_raw(5),
+            },
+        ],
+        target: Target::Offscreen(Attachment {
+            image: vk::Image::from_raw(6),
+            view: vk::ImageView::from_raw(7),
+        }),
+    };
+    unsafe {
+        renderer.destroy_self(&recorder);
+        vk::Buffer::from_raw(8).destroy_self(&recorder);
+    }
+
+    let expected = r#"[
+  {"object_type": "IMAGE_VIEW", "handle": 7, "path": "target.Offscreen.0.view"},
+  {"object_type": "IMAGE", "handle": 6, "path": "target.Offscreen.0.image"},
+  {"object_type": "IMAGE_VIEW", "handle": 5, "path": "attachments[1].view"},
+  {"object_type": "IMAGE", "handle": 4, "path": "attachments[1].image"},
+  {"object_type": "IMAGE_VIEW", "handle": 3, "path": "attachments[0].view"},
+  {"object_type": "IMAGE", "handle": 2, "path": "attachments[0].image"},
+  {"object_type": "RENDER_PASS", "handle": 1, "path": "render_pass"},
+  {"object_type": "BUFFER", "handle": 8, "path": ""}
+]"#;
+    assert_eq!(recorder.to_json(), expected);
+
+    // the calls are forwarded to the wrapped device
+    assert_eq!(mock.calls().len(), 8);
+
+    assert_eq!(
+        recorder.take_entries()[0],
+        DestructionEntry {
+            object_type: vk::ObjectType::IMAGE_VIEW,
+            handle: 7,
+            path: "target.Offscreen.0.view".to_string(),
+        }
+    );
+    assert_eq!(recorder.to_json(), "[]");
+
+    // destroying through the device of a dropped recorder is reported instead of aborting
+    let device = recorder.device().clone();
+    drop(recorder);
+    mock.take_calls();
+    unsafe { vk::Sampler::from_raw(9).destroy_self(&device) };
+    assert_eq!(mock.take_calls(), []);
+    assert_eq!(
+        DestructionRecorder::take_stray_entries(),
+        [DestructionEntry {
+            object_type: vk::ObjectType::SAMPLER,
+            handle: 9,
+            path: String::new(),
+        }]
+    );
+    assert_eq!(DestructionRecorder::take_stray_entries(), []);
+}
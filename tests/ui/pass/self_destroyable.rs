@@ -0,0 +1,67 @@
+use ash::vk::{self, Handle};
+use ash_destructor::{
+    testing::{DestroyCall, MockDevice},
+    DeviceDestroyable, SelfDestroyable,
+};
+
+#[derive(SelfDestroyable)]
+struct GpuContext {
+    #[destroy_device]
+    device: ash::Device,
+    pools: Vec<vk::CommandPool>,
+    #[destroy_ignore]
+    _name: String,
+}
+
+#[derive(SelfDestroyable)]
+struct DeviceOnly(#[destroy_device] ash::Device);
+
+#[derive(DeviceDestroyable)]
+struct Contexts {
+    first: GpuContext,
+    second: DeviceOnly,
+}
+
+fn main() {
+    let mock = MockDevice::new();
+    let device = mock.device().handle();
+    let context = GpuContext {
+        device: mock.device().clone(),
+        pools: vec![vk::CommandPool::from_raw(1), vk::CommandPool::from_raw(2)],
+        _name: "main".to_string(),
+    };
+    unsafe {
+        SelfDestroyable::destroy_self(&context);
+    }
+    assert_eq!(
+        mock.take_calls(),
+        [
+            DestroyCall::new(vk::CommandPool::from_raw(2), None),
+            DestroyCall::new(vk::CommandPool::from_raw(1), None),
+            DestroyCall::new(device, None),
+        ]
+    );
+
+    // the device given to DeviceDestroyable is ignored in favor of the owned one
+    let other = MockDevice::new();
+    let contexts = Contexts {
+        first: GpuContext {
+            device: mock.device().clone(),
+            pools: vec![vk::CommandPool::from_raw(3)],
+            _name: "first".to_string(),
+        },
+        second: DeviceOnly(mock.device().clone()),
+    };
+    unsafe {
+        contexts.destroy_self(&other);
+    }
+    assert_eq!(
+        mock.take_calls(),
+        [
+            DestroyCall::new(device, None),
+            DestroyCall::new(vk::CommandPool::from_raw(3), None),
+            DestroyCall::new(device, None),
+        ]
+    );
+    assert!(other.calls().is_empty());
+}
@@ -0,0 +1,136 @@
+use std::{
+    cell::{Cell, RefCell},
+    mem::ManuallyDrop,
+    sync::{Arc, LazyLock, Mutex, OnceLock, RwLock},
+};
+
+use ash::vk::{self, Handle};
+use ash_destructor::{
+    testing::{DestructionRecorder, MockDevice},
+    DeviceDestroyable, Shared,
+};
+
+static SAMPLER: LazyLock<vk::Sampler> = LazyLock::new(|| vk::Sampler::from_raw(9));
+
+#[derive(DeviceDestroyable)]
+struct Material {
+    layout: Shared<vk::PipelineLayout>,
+    set_layout: Shared<vk::DescriptorSetLayout>,
+    sampler: OnceLock<vk::Sampler>,
+    unused_sampler: OnceLock<vk::Sampler>,
+}
+
+#[derive(DeviceDestroyable)]
+struct Scene {
+    a: Material,
+    b: Material,
+}
+
+fn main() {
+    let mock = MockDevice::new();
+    let recorder = unsafe { DestructionRecorder::wrap(&mock) };
+    let destroyed = || {
+        recorder
+            .take_entries()
+            .iter()
+            .map(|entry| (entry.object_type, entry.handle))
+            .collect::<Vec<_>>()
+    };
+
+    let layout = Shared::new(vk::PipelineLayout::from_raw(1));
+    let set_layout = Shared::new(vk::DescriptorSetLayout::from_raw(2));
+    let first = Material {
+        layout: layout.clone(),
+        set_layout: set_layout.clone(),
+        sampler: OnceLock::from(vk::Sampler::from_raw(3)),
+        unused_sampler: OnceLock::new(),
+    };
+    let second = Material {
+        layout,
+        set_layout,
+        sampler: OnceLock::from(vk::Sampler::from_raw(4)),
+        unused_sampler: OnceLock::new(),
+    };
+    // the layouts are still owned by the second material
+    unsafe { first.destroy_self(&recorder) };
+    assert_eq!(destroyed(), [(vk::ObjectType::SAMPLER, 3)]);
+    assert_eq!(Shared::owners(&second.layout), 1);
+    // destroying the first material again doesn't give up the share of the second one
+    unsafe { first.destroy_self(&recorder) };
+    assert_eq!(destroyed(), [(vk::ObjectType::SAMPLER, 3)]);
+    unsafe { second.destroy_self(&recorder) };
+    assert_eq!(
+        destroyed(),
+        [
+            (vk::ObjectType::SAMPLER, 4),
+            (vk::ObjectType::DESCRIPTOR_SET_LAYOUT, 2),
+            (vk::ObjectType::PIPELINE_LAYOUT, 1),
+        ]
+    );
+    assert!(Shared::is_destroyed(&first.layout));
+
+    // both owners within a single value
+    let layout = Shared::new(vk::PipelineLayout::from_raw(11));
+    let set_layout = Shared::new(vk::DescriptorSetLayout::from_raw(12));
+    let scene = Scene {
+        a: Material {
+            layout: layout.clone(),
+            set_layout: set_layout.clone(),
+            sampler: OnceLock::new(),
+            unused_sampler: OnceLock::new(),
+        },
+        b: Material {
+            layout,
+            set_layout,
+            sampler: OnceLock::new(),
+            unused_sampler: OnceLock::new(),
+        },
+    };
+    unsafe { scene.destroy_self(&recorder) };
+    assert_eq!(
+        destroyed(),
+        [
+            (vk::ObjectType::DESCRIPTOR_SET_LAYOUT, 12),
+            (vk::ObjectType::PIPELINE_LAYOUT, 11),
+        ]
+    );
+
+    // dropping an owner gives up its share
+    let buffer = Shared::new(vk::Buffer::from_raw(13));
+    drop(buffer.clone());
+    unsafe { buffer.destroy_self(&recorder) };
+    assert_eq!(destroyed(), [(vk::ObjectType::BUFFER, 13)]);
+
+    let poisoned = Arc::new(Mutex::new(vk::Buffer::from_raw(5)));
+    std::panic::set_hook(Box::new(|_| {}));
+    let _ = std::thread::spawn({
+        let poisoned = poisoned.clone();
+        move || {
+            let _guard = poisoned.lock().unwrap();
+            panic!("poisoning the mutex");
+        }
+    })
+    .join();
+    let _ = std::panic::take_hook();
+    assert!(poisoned.is_poisoned());
+    unsafe {
+        (*poisoned).destroy_self(&recorder);
+        RwLock::new(vk::Image::from_raw(6)).destroy_self(&recorder);
+        RefCell::new(vk::Fence::from_raw(7)).destroy_self(&recorder);
+        Cell::new(vk::Semaphore::from_raw(8)).destroy_self(&recorder);
+        LazyLock::force(&SAMPLER);
+        SAMPLER.destroy_self(&recorder);
+        ManuallyDrop::new(vk::Event::from_raw(10)).destroy_self(&recorder);
+    }
+    assert_eq!(
+        destroyed(),
+        [
+            (vk::ObjectType::BUFFER, 5),
+            (vk::ObjectType::IMAGE, 6),
+            (vk::ObjectType::FENCE, 7),
+            (vk::ObjectType::SEMAPHORE, 8),
+            (vk::ObjectType::SAMPLER, 9),
+            (vk::ObjectType::EVENT, 10),
+        ]
+    );
+}
@@ -0,0 +1,87 @@
+use std::{collections::BTreeMap, fmt, sync::Mutex};
+
+use ash::vk::{self, Handle};
+use ash_destructor::{testing::MockDevice, DeviceDestroyable};
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Metadata, Subscriber,
+};
+
+static EVENTS: Mutex<Vec<BTreeMap<&'static str, String>>> = Mutex::new(Vec::new());
+
+// records the fields of every event of the crate
+struct Capture;
+
+struct Fields(BTreeMap<&'static str, String>);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(field.name(), format!("{value:?}"));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name(), value.to_owned());
+    }
+}
+
+impl Subscriber for Capture {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.target() == "ash_destructor"
+    }
+
+    fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+        span::Id::from_u64(1)
+    }
+
+    fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(BTreeMap::new());
+        event.record(&mut fields);
+        EVENTS.lock().unwrap().push(fields.0);
+    }
+
+    fn enter(&self, _: &span::Id) {}
+
+    fn exit(&self, _: &span::Id) {}
+}
+
+#[derive(DeviceDestroyable)]
+struct Frame {
+    fences: (vk::Fence, vk::Fence),
+}
+
+fn main() {
+    tracing::subscriber::set_global_default(Capture).unwrap();
+
+    let mock = MockDevice::new();
+    let device = format!("0x{:x}", mock.handle().as_raw());
+    let frames = vec![Frame {
+        fences: (vk::Fence::from_raw(1), vk::Fence::from_raw(2)),
+    }];
+    unsafe {
+        frames.destroy_self(&mock);
+        vk::Sampler::from_raw(3).destroy_self(&mock);
+    }
+
+    let event = |object_type: &str, handle: &str, owner: &str, path: &str| {
+        BTreeMap::from([
+            ("message", format!("destroying {object_type}")),
+            ("object_type", object_type.to_owned()),
+            ("handle", handle.to_owned()),
+            ("parent", device.clone()),
+            ("owner", owner.to_owned()),
+            ("path", path.to_owned()),
+        ])
+    };
+    assert_eq!(
+        *EVENTS.lock().unwrap(),
+        [
+            event("FENCE", "0x2", "Frame", "[0].fences[1]"),
+            event("FENCE", "0x1", "Frame", "[0].fences[0]"),
+            event("SAMPLER", "0x3", "", ""),
+        ]
+    );
+}
@@ -0,0 +1,50 @@
+use ash::vk::{self, Handle};
+use ash_destructor::{
+    testing::{DestroyCall, MockDevice},
+    try_destroy_all, try_destroy_all_after_queues, DestroyError, DestroyStep,
+};
+
+fn main() {
+    let mock = MockDevice::new();
+    let graphics = vk::Queue::from_raw(1);
+    let transfer = vk::Queue::from_raw(2);
+    let buffers = [vk::Buffer::from_raw(3), vk::Buffer::from_raw(4)];
+    let image = vk::Image::from_raw(5);
+
+    unsafe {
+        assert_eq!(try_destroy_all(&mock, &buffers), Ok(()));
+        assert_eq!(try_destroy_all_after_queues(&mock, &[graphics, transfer], &image), Ok(()));
+    }
+    assert_eq!(
+        mock.take_calls(),
+        [
+            DestroyCall::new(buffers[1], None),
+            DestroyCall::new(buffers[0], None),
+            DestroyCall::new(image, None),
+        ]
+    );
+
+    // nothing is destroyed when a wait fails, and the failed wait is reported
+    mock.set_wait_result(vk::Result::ERROR_DEVICE_LOST);
+    mock.set_queue_wait_result(transfer, vk::Result::ERROR_OUT_OF_DEVICE_MEMORY);
+    unsafe {
+        let device_error = try_destroy_all(&mock, &buffers).unwrap_err();
+        assert_eq!(
+            device_error,
+            DestroyError {
+                step: DestroyStep::DeviceWaitIdle,
+                result: vk::Result::ERROR_DEVICE_LOST,
+            }
+        );
+        assert_eq!(device_error.to_string(), "waiting for the device to be idle failed with ERROR_DEVICE_LOST");
+
+        let queue_error = try_destroy_all_after_queues(&mock, &[graphics, transfer], &buffers).unwrap_err();
+        assert_eq!(queue_error.step, DestroyStep::QueueWaitIdle(transfer));
+        assert_eq!(vk::Result::from(queue_error), vk::Result::ERROR_OUT_OF_DEVICE_MEMORY);
+        assert_eq!(
+            queue_error.to_string(),
+            "waiting for queue 0x2 to be idle failed with ERROR_OUT_OF_DEVICE_MEMORY"
+        );
+    }
+    assert!(mock.calls().is_empty());
+}
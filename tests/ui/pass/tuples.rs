@@ -0,0 +1,58 @@
+use ash::vk::{self, Handle};
+use ash_destructor::{
+    testing::{DestructionRecorder, MockDevice},
+    DeviceDestroyable,
+};
+
+fn create_image(id: u64) -> (vk::Image, vk::DeviceMemory, vk::ImageView) {
+    (
+        vk::Image::from_raw(id),
+        vk::DeviceMemory::from_raw(id + 1),
+        vk::ImageView::from_raw(id + 2),
+    )
+}
+
+fn main() {
+    let mock = MockDevice::new();
+    let recorder = unsafe { DestructionRecorder::wrap(&mock) };
+
+    let image = create_image(1);
+    let nested = (vk::Buffer::from_raw(4), vec![create_image(5)], Some(vk::Sampler::from_raw(8)));
+    unsafe {
+        image.destroy_self(&recorder);
+        nested.destroy_self(&recorder);
+        (
+            vk::Fence::from_raw(9),
+            vk::Fence::from_raw(10),
+            vk::Fence::from_raw(11),
+            vk::Fence::from_raw(12),
+            vk::Fence::from_raw(13),
+            vk::Fence::from_raw(14),
+            vk::Fence::from_raw(15),
+            vk::Fence::from_raw(16),
+            vk::Fence::from_raw(17),
+            vk::Fence::from_raw(18),
+            vk::Fence::from_raw(19),
+            vk::Fence::from_raw(20),
+        )
+            .destroy_self(&recorder);
+    }
+
+    let destroyed = recorder
+        .entries()
+        .iter()
+        .map(|entry| (entry.object_type, entry.handle))
+        .collect::<Vec<_>>();
+    let mut expected = vec![
+        (vk::ObjectType::IMAGE_VIEW, 3),
+        (vk::ObjectType::DEVICE_MEMORY, 2),
+        (vk::ObjectType::IMAGE, 1),
+        (vk::ObjectType::SAMPLER, 8),
+        (vk::ObjectType::IMAGE_VIEW, 7),
+        (vk::ObjectType::DEVICE_MEMORY, 6),
+        (vk::ObjectType::IMAGE, 5),
+        (vk::ObjectType::BUFFER, 4),
+    ];
+    expected.extend((9..=20).rev().map(|fence| (vk::ObjectType::FENCE, fence)));
+    assert_eq!(destroyed, expected);
+}
@@ -0,0 +1,43 @@
+use ash::vk::{self, Handle};
+use ash_destructor::{
+    destroy_after_fence, destroy_after_timeline_alloc,
+    testing::{DestroyCall, MockDevice},
+};
+
+fn main() {
+    let mock = MockDevice::new();
+    let fence = vk::Fence::from_raw(1);
+    let semaphore = vk::Semaphore::from_raw(2);
+    let buffers = [vk::Buffer::from_raw(3), vk::Buffer::from_raw(4)];
+
+    unsafe {
+        assert_eq!(destroy_after_fence(&mock, fence, u64::MAX, &buffers[0]), Ok(()));
+    }
+    assert_eq!(mock.take_calls(), [DestroyCall::new(buffers[0], None)]);
+
+    // nothing is destroyed when the wait fails
+    for result in [vk::Result::TIMEOUT, vk::Result::ERROR_DEVICE_LOST] {
+        mock.set_wait_result(result);
+        unsafe {
+            assert_eq!(destroy_after_fence(&mock, fence, 0, &buffers), Err(result));
+            assert_eq!(destroy_after_timeline_alloc(&mock, semaphore, 5, 0, &buffers, None), Err(result));
+        }
+        assert!(mock.calls().is_empty());
+    }
+
+    mock.set_wait_result(vk::Result::SUCCESS);
+    let allocation_callbacks = vk::AllocationCallbacks::default();
+    unsafe {
+        assert_eq!(
+            destroy_after_timeline_alloc(&mock, semaphore, 5, u64::MAX, &buffers, Some(&allocation_callbacks)),
+            Ok(())
+        );
+    }
+    assert_eq!(
+        mock.take_calls(),
+        [
+            DestroyCall::new(buffers[1], Some(&allocation_callbacks)),
+            DestroyCall::new(buffers[0], Some(&allocation_callbacks)),
+        ]
+    );
+}
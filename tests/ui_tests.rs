@@ -1,7 +1,9 @@
-
 #[test]
 fn ui_tests() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/ui/fail/*.rs");
     t.pass("tests/ui/pass/*.rs");
+    // not enabled by the dev-dependencies, run with `--features debug-checks`
+    #[cfg(feature = "debug-checks")]
+    t.pass("tests/ui/debug_checks/*.rs");
 }